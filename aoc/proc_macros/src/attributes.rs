@@ -0,0 +1,150 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{ParseStream, Parser};
+use syn::{Error, FnArg, ItemFn, LitInt, ReturnType, Signature, Token, Type};
+
+pub fn part(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let function: ItemFn = syn::parse2(item)?;
+    let (part, overrides) = parse_part_args(attr)?;
+    let raw_input = check_part_signature(&function.sig)?;
+    let marker = registration_marker(&format!("PART_{}", part), &function.sig);
+
+    let name = &function.sig.ident;
+    if part == 2 {
+        return Ok(quote! {
+            #function
+            #marker
+            #[allow(unused_imports)]
+            use self::#name as __aoc_part_2;
+        });
+    }
+    let run = if raw_input {
+        quote! {
+            ::aoc::registry::no_parse_fn(&__aoc_parse);
+            ::aoc::main_parts(
+                ::aoc::year!(#overrides), ::aoc::day!(#overrides),
+                |input: &str| ::aoc::__chain_errors!(#name(input)),
+                |input: &str| ::aoc::__chain_errors!(__aoc_part_2(input)))
+        }
+    } else {
+        quote! {
+            ::aoc::main_registered(
+                ::aoc::year!(#overrides), ::aoc::day!(#overrides),
+                |input: &str| ::aoc::__chain_errors!(::aoc::registry::ParseFn::parse(&__aoc_parse, input)),
+                |parsed: &_| ::aoc::__chain_errors!(#name(parsed)),
+                |parsed: &_| ::aoc::__chain_errors!(__aoc_part_2(parsed)))
+        }
+    };
+    Ok(quote! {
+        #function
+        #marker
+        #[allow(unused_imports)]
+        use ::aoc::registry::*;
+        fn main() {
+            ::aoc::set_manifest_dir(env!("CARGO_MANIFEST_DIR"));
+            ::aoc::set_package_name(env!("CARGO_PKG_NAME"));
+            #run;
+        }
+    })
+}
+
+pub fn parse(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let function: ItemFn = syn::parse2(item)?;
+    if !attr.is_empty() {
+        return Err(Error::new_spanned(attr, "`#[aoc::parse]` takes no arguments"));
+    }
+    let message = "the parse function must take a single argument, the input as `&str`";
+    check_common_signature(&function.sig, "the parse function")?;
+    let input = single_reference_argument(&function.sig, message)?;
+    if !is_str(input) {
+        return Err(Error::new_spanned(input, message));
+    }
+    let marker = registration_marker("PARSE_FUNCTION", &function.sig);
+
+    let name = &function.sig.ident;
+    Ok(quote! {
+        #function
+        #marker
+        #[allow(unused_imports)]
+        use self::#name as __aoc_parse;
+    })
+}
+
+/// Parses the arguments of `#[aoc::part]`: the part number, followed for part 1 by the overrides
+/// that `aoc::main!` takes (e.g. `day = 3`), which are returned to pass on to `year!` and `day!`.
+fn parse_part_args(attr: TokenStream) -> syn::Result<(u32, TokenStream)> {
+    let message = "expected a part number: `#[aoc::part(1)]` or `#[aoc::part(2)]`";
+    let span = if attr.is_empty() { Span::call_site() } else { attr.clone().into_iter().next().unwrap().span() };
+    let (number, overrides) = (|input: ParseStream| {
+        let number: LitInt = input.parse()?;
+        let overrides = match input.parse::<Option<Token![,]>>()? {
+            Some(_) => input.parse::<TokenStream>()?,
+            None => TokenStream::new(),
+        };
+        Ok((number, overrides))
+    }).parse2(attr).map_err(|_| Error::new(span, message))?;
+    match number.base10_parse()? {
+        1 => Ok((1, overrides)),
+        2 if overrides.is_empty() => Ok((2, overrides)),
+        2 => Err(Error::new_spanned(overrides, "only `#[aoc::part(1)]` takes a year and day")),
+        _ => Err(Error::new_spanned(number, message)),
+    }
+}
+
+/// Checks that a part function has a valid signature, and returns whether it takes the raw input
+/// (`&str`) rather than the output of the parse function.
+fn check_part_signature(sig: &Signature) -> syn::Result<bool> {
+    check_common_signature(sig, "a part function")?;
+    let input = single_reference_argument(
+        sig,
+        "a part function must take a single argument by reference: the input as `&str`, or the \
+        output of the `#[aoc::parse]` function, e.g. `&Input`")?;
+    Ok(is_str(input))
+}
+
+fn check_common_signature(sig: &Signature, what: &str) -> syn::Result<()> {
+    if let Some(param) = sig.generics.params.first() {
+        return Err(Error::new_spanned(param, format!("{} cannot be generic", what)));
+    }
+    if let Some(asyncness) = sig.asyncness {
+        return Err(Error::new_spanned(asyncness, format!("{} cannot be async", what)));
+    }
+    if let ReturnType::Default = sig.output {
+        return Err(Error::new_spanned(&sig.ident, format!("{} must return a value", what)));
+    }
+    Ok(())
+}
+
+/// Returns the referenced type of the single argument, which must be a shared reference.
+fn single_reference_argument<'a>(sig: &'a Signature, message: &str) -> syn::Result<&'a Type> {
+    if sig.inputs.len() != 1 {
+        return Err(match sig.inputs.is_empty() {
+            true => Error::new_spanned(&sig.ident, message),
+            false => Error::new_spanned(&sig.inputs, message),
+        });
+    }
+    match &sig.inputs[0] {
+        FnArg::Typed(arg) => match &*arg.ty {
+            Type::Reference(reference) if reference.mutability.is_none() => Ok(&reference.elem),
+            ty => Err(Error::new_spanned(ty, message)),
+        },
+        receiver => Err(Error::new_spanned(receiver, message)),
+    }
+}
+
+fn is_str(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident("str"))
+}
+
+/// Returns an item with a fixed name for what is being registered, e.g. `PART_1`, so that if two
+/// functions are registered as the same thing, rustc reports that this name is defined twice, at
+/// the names of both functions. This works without remembering anything between expansions, which
+/// is not reliable in proc macros.
+fn registration_marker(what: &str, sig: &Signature) -> TokenStream {
+    let name = format_ident!("__AOC_{}_IS_ALREADY_REGISTERED", what, span = sig.ident.span());
+    quote! {
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        const #name: () = ();
+    }
+}
@@ -0,0 +1,215 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parenthesized, Error, Expr, ExprLit, ExprUnary, Ident, Lit, LitInt, LitStr, Token, UnOp};
+
+/// How the solution under test is given, like the forms of `aoc::main!`.
+enum Solution {
+    Whole(Expr),
+    Parts(Expr, Expr),
+    Parsed(Expr, Expr, Expr),
+}
+
+struct Example {
+    index: LitInt,
+    /// The expected answer to each part, or `None` for `_`.
+    answers: Vec<Option<Expr>>,
+}
+
+struct ExampleTests {
+    solution: Solution,
+    examples: Vec<Example>,
+    /// The year and day if given after the examples, e.g. `day = 3`, passed on to `year!` and `day!`.
+    overrides: TokenStream,
+}
+
+impl Parse for ExampleTests {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let first: Expr = input.parse()?;
+        let solution = if input.parse::<Option<Token![=>]>>()?.is_some() {
+            let part_1 = input.parse()?;
+            input.parse::<Token![,]>()?;
+            Solution::Parsed(first, part_1, input.parse()?)
+        } else if input.peek(Token![,]) && !starts_example(input, 1) {
+            input.parse::<Token![,]>()?;
+            Solution::Parts(first, input.parse()?)
+        } else {
+            Solution::Whole(first)
+        };
+
+        let mut examples = Vec::new();
+        let mut overrides = TokenStream::new();
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            if input.peek(Ident) {
+                overrides = input.parse()?;
+                break;
+            }
+            examples.push(input.parse()?);
+        }
+        if !input.is_empty() {
+            return Err(input.error("expected `,` followed by an example, e.g. `0 => (13, 43)`"));
+        }
+        if examples.is_empty() {
+            return Err(input.error("expected at least one example, e.g. `0 => (13, 43)`"));
+        }
+        Ok(ExampleTests { solution, examples, overrides })
+    }
+}
+
+impl Parse for Example {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let index = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let content;
+        parenthesized!(content in input);
+        let answers = Punctuated::<ExpectedAnswer, Token![,]>::parse_terminated(&content)?;
+        if answers.is_empty() || answers.len() > 2 {
+            return Err(Error::new(content.span(), "expected the answers to part 1 and optionally part 2"));
+        }
+        Ok(Example { index, answers: answers.into_iter().map(|answer| answer.0).collect() })
+    }
+}
+
+struct ExpectedAnswer(Option<Expr>);
+
+impl Parse for ExpectedAnswer {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.parse::<Option<Token![_]>>()?.is_some() {
+            Ok(ExpectedAnswer(None))
+        } else {
+            Ok(ExpectedAnswer(Some(input.parse()?)))
+        }
+    }
+}
+
+/// Returns whether an example (`N =>`) follows after skipping the given number of tokens.
+fn starts_example(input: ParseStream, skip: usize) -> bool {
+    let fork = input.fork();
+    for _ in 0..skip {
+        if fork.parse::<proc_macro2::TokenTree>().is_err() {
+            return false;
+        }
+    }
+    fork.parse::<LitInt>().is_ok() && fork.peek(Token![=>])
+}
+
+/// Turns an unsuffixed integer literal into a string, because it would default to `i32`, which
+/// does not fit many answers.
+fn expected_answer(expected: Expr) -> TokenStream {
+    let (negative, literal) = match &expected {
+        Expr::Lit(ExprLit { lit: Lit::Int(literal), .. }) => (false, literal),
+        Expr::Unary(ExprUnary { op: UnOp::Neg(_), expr, .. }) => match &**expr {
+            Expr::Lit(ExprLit { lit: Lit::Int(literal), .. }) => (true, literal),
+            _ => return quote!(#expected),
+        },
+        _ => return quote!(#expected),
+    };
+    if !literal.suffix().is_empty() {
+        return quote!(#expected);
+    }
+    let sign = if negative { "-" } else { "" };
+    let text = LitStr::new(&format!("{}{}", sign, literal.base10_digits()), literal.span());
+    quote!(#text)
+}
+
+pub fn example_tests(input: TokenStream) -> syn::Result<TokenStream> {
+    let ExampleTests { solution, examples, overrides } = syn::parse2(input)?;
+    let mut tests = TokenStream::new();
+    for Example { index, answers } in examples {
+        let index_value = index.base10_parse::<usize>()?;
+        for (part, expected) in (1u32..).zip(answers) {
+            let Some(expected) = expected else { continue };
+            let expected = expected_answer(expected);
+            let name = format_ident!("example_{}_part_{}", index_value, part);
+            let answer = match &solution {
+                Solution::Whole(run) => {
+                    let position = part as usize - 1;
+                    quote!(::aoc::Answer::parts(&(#run)(input)).into_iter().nth(#position).flatten())
+                }
+                Solution::Parts(part_1, part_2) => {
+                    let part_fn = if part == 1 { part_1 } else { part_2 };
+                    quote!(::aoc::Answer::part(&(#part_fn)(input)))
+                }
+                Solution::Parsed(parse, part_1, part_2) => {
+                    let part_fn = if part == 1 { part_1 } else { part_2 };
+                    quote!(match ::aoc::ParseOutput::into_parsed(::aoc::__chain_errors!((#parse)(input))) {
+                        Ok(parsed) => ::aoc::Answer::part(&(#part_fn)(&parsed)),
+                        Err(message) => panic!("failed to parse the example: {}", message),
+                    })
+                }
+            };
+            tests.extend(quote! {
+                #[test]
+                fn #name() {
+                    ::aoc::set_manifest_dir(env!("CARGO_MANIFEST_DIR"));
+                    ::aoc::set_package_name(env!("CARGO_PKG_NAME"));
+                    ::aoc::assert_example_part(
+                        ::aoc::year!(#overrides), ::aoc::day!(#overrides), #index, #part, |input: &str| #answer, #expected);
+                }
+            });
+        }
+    }
+    Ok(tests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: TokenStream) -> syn::Result<ExampleTests> {
+        syn::parse2(input)
+    }
+
+    #[test]
+    fn solution_forms() {
+        assert!(matches!(parse(quote!(run, 0 => (1, 2))).unwrap().solution, Solution::Whole(_)));
+        assert!(matches!(parse(quote!(part_1, part_2, 0 => (1, 2))).unwrap().solution, Solution::Parts(..)));
+        assert!(matches!(parse(quote!(parse => part_1, part_2, 0 => (1, 2))).unwrap().solution, Solution::Parsed(..)));
+        let tests = parse(quote!(|input| part_1(input, 10), part_2, 0 => (40, _), 1 => (_, 25272))).unwrap();
+        assert!(matches!(&tests.solution, Solution::Parts(Expr::Closure(_), _)));
+        assert_eq!(tests.examples.len(), 2);
+        assert!(tests.examples[0].answers[1].is_none());
+        assert!(tests.examples[1].answers[0].is_none());
+        assert!(tests.overrides.is_empty());
+    }
+
+    #[test]
+    fn overrides() {
+        let tests = parse(quote!(run, 0 => (1, 2), 1 => (3, _), year = 2024, day = 3)).unwrap();
+        assert_eq!(tests.examples.len(), 2);
+        assert_eq!(tests.overrides.to_string(), "year = 2024 , day = 3");
+        let tests = example_tests(quote!(run, 0 => (1, 2), day = 3,)).unwrap().to_string();
+        assert!(tests.contains(":: aoc :: day ! (day = 3 ,)"));
+        assert!(parse(quote!(run, day = 3)).is_err());
+    }
+
+    #[test]
+    fn expected_answers() {
+        let answer = |expected: TokenStream| expected_answer(syn::parse2(expected).unwrap()).to_string();
+        assert_eq!(answer(quote!(4174379265)), "\"4174379265\"");
+        assert_eq!(answer(quote!(-12)), "\"-12\"");
+        assert_eq!(answer(quote!(12u8)), "12u8");
+        assert_eq!(answer(quote!("#..#")), "\"#..#\"");
+        assert_eq!(answer(quote!(ANSWER)), "ANSWER");
+    }
+
+    #[test]
+    fn generated_tests() {
+        let tests = example_tests(quote!(parse => part_1, part_2, 0 => (13, _), 2 => (_, 43))).unwrap().to_string();
+        assert!(tests.contains("fn example_0_part_1"));
+        assert!(!tests.contains("fn example_0_part_2"));
+        assert!(tests.contains("fn example_2_part_2"));
+        assert!(tests.contains("\"43\""));
+    }
+
+    #[test]
+    fn errors() {
+        let error = |input: TokenStream| parse(input).err().unwrap().to_string();
+        assert_eq!(error(quote!(run)), "unexpected end of input, expected at least one example, e.g. `0 => (13, 43)`");
+        assert_eq!(error(quote!(run, 0 => ())), "expected the answers to part 1 and optionally part 2");
+        assert_eq!(error(quote!(run, 0 => (1, 2, 3))), "expected the answers to part 1 and optionally part 2");
+        assert!(parse(quote!(run, 0 => (1, 2) 1 => (3, 4))).is_err());
+        assert!(parse(quote!(parse =>, 0 => (1, 2))).is_err());
+    }
+}
@@ -1,47 +1,81 @@
-use once_cell::sync::Lazy;
 use proc_macro::TokenStream;
 use quote::quote;
-use regex::Regex;
-use std::env;
 
-static YEAR_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"2\d{3}").unwrap());
-static DAY_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d{2}").unwrap());
+mod attributes;
+mod example_tests;
+mod names;
+#[path = "../../src/names.rs"]
+mod shared_names;
 
-/// Parses the year from the package name of the crate that is currently being compiled (i.e. the
-/// crate that invoked the macro).
+/// Determines the year of the crate that is currently being compiled (i.e. the crate that invoked
+/// the macro): from a binary name like `2024_03`, or else from the package name, e.g. `aoc2025`.
+/// Pass `year = YYYY` to override it.
 #[proc_macro]
-pub fn year(_item: TokenStream) -> TokenStream {
-    handle_errors(|| {
-        let pkg_name = env_var("CARGO_PKG_NAME")?;
-        let year = YEAR_REGEX
-            .find(&pkg_name)
-            .ok_or_else(|| format!("no year found in package name {pkg_name}"))?
-            .as_str()
-            .parse::<u32>()
-            .unwrap();
-        Ok(quote!(#year).into())
-    })
+pub fn year(input: TokenStream) -> TokenStream {
+    handle_errors(|| names::year(input.into()))
 }
 
+/// Determines the day from the name of the binary that is currently being compiled. Accepts names
+/// like `01`, `1`, `day1`, `day01_alt` and `2024_03`, and checks that the day is part of the
+/// event. Pass `day = N` to override it.
 #[proc_macro]
-pub fn day(_item: TokenStream) -> TokenStream {
-    handle_errors(|| {
-        let bin_name = env_var("CARGO_BIN_NAME")?;
-        let day = DAY_REGEX 
-            .find(&bin_name)
-            .ok_or_else(|| format!("no day found in binary name {bin_name}"))?
-            .as_str()
-            .parse::<u32>()
-            .unwrap();
-        Ok(quote!(#day).into())
-    })
+pub fn day(input: TokenStream) -> TokenStream {
+    handle_errors(|| names::day(input.into()))
 }
 
-fn env_var(name: &str) -> Result<String, String> {
-    env::var(name)
-        .map_err(|_| format!("{name} not set in environment"))
+/// Registers a function as the solution to part 1 or 2, e.g. `#[aoc::part(1)]`. The function takes
+/// the raw input as `&str`, or the output of the `#[aoc::parse]` function by reference, and returns
+/// an answer. Part 1 also generates `fn main()`, which runs everything that is registered; part 2
+/// is reported as unsolved until it is registered. Like `aoc::main!`, part 1 takes the year and day
+/// if the names do not say, e.g. `#[aoc::part(1, day = 3)]`.
+#[proc_macro_attribute]
+pub fn part(attr: TokenStream, item: TokenStream) -> TokenStream {
+    handle_item_errors(item.clone(), || attributes::part(attr.into(), item.into()))
 }
 
-fn handle_errors(mut f: impl FnMut() -> Result<TokenStream, String>) -> TokenStream {
-    f().unwrap_or_else(|message| quote!(compile_error!(#message);).into())
+/// Registers a function that parses the input (`&str`) once, before its output is passed to the
+/// `#[aoc::part]` functions by reference.
+#[proc_macro_attribute]
+pub fn parse(attr: TokenStream, item: TokenStream) -> TokenStream {
+    handle_item_errors(item.clone(), || attributes::parse(attr.into(), item.into()))
+}
+
+/// Generates a test for each part of each example, named e.g. `example_1_part_2`. The solution is
+/// given like to `aoc::main!`, followed by the expected answers per example; `_` skips a part:
+///
+/// ```ignore
+/// aoc::example_tests!(run, 0 => (13, 43), 1 => (_, 40));
+/// aoc::example_tests!(parse => part_1, part_2, 0 => (13, 43));
+/// ```
+///
+/// Each function can also be a closure. Use this when a part takes extra parameters whose value is
+/// different for the examples than for the puzzle input, like the number of steps to simulate:
+///
+/// ```ignore
+/// fn part_1(input: &str, steps: usize) -> u64 { ... }
+///
+/// aoc::main!(|input| part_1(input, 1000), part_2);
+/// aoc::example_tests!(|input| part_1(input, 10), part_2, 0 => (40, 25272));
+/// ```
+///
+/// Unsuffixed integers are compared as text, so answers that do not fit in an `i32` need no suffix.
+/// If `aoc::main!` is given the year or day, pass them after the examples too, e.g.
+/// `aoc::example_tests!(run, 0 => (13, 43), day = 3)`.
+#[proc_macro]
+pub fn example_tests(input: TokenStream) -> TokenStream {
+    handle_errors(|| example_tests::example_tests(input.into()))
+}
+
+fn handle_errors(f: impl FnOnce() -> syn::Result<proc_macro2::TokenStream>) -> TokenStream {
+    f().unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+/// Like `handle_errors`, but for attribute macros: the error points at the offending tokens, and
+/// the item is kept so that its uses do not cause more errors.
+fn handle_item_errors(item: TokenStream, f: impl FnOnce() -> syn::Result<proc_macro2::TokenStream>) -> TokenStream {
+    f().unwrap_or_else(|err| {
+        let error = err.to_compile_error();
+        let item = proc_macro2::TokenStream::from(item);
+        quote!(#error #item)
+    }).into()
 }
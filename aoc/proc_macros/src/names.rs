@@ -0,0 +1,99 @@
+use std::env;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Error, Ident, LitInt, Token};
+
+use crate::shared_names::{num_days, parse_bin_name, parse_package_name, parse_year, FIRST_YEAR};
+
+/// Values given explicitly to `aoc::main!`, e.g. `year = 2024, day = 3`.
+#[derive(Default)]
+struct Overrides {
+    year: Option<LitInt>,
+    day: Option<LitInt>,
+}
+
+impl Parse for Overrides {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut overrides = Overrides::default();
+        let pairs = Punctuated::<(Ident, LitInt), Token![,]>::parse_terminated_with(input, |input| {
+            let key = input.parse()?;
+            input.parse::<Token![=]>()?;
+            Ok((key, input.parse()?))
+        })?;
+        for (key, value) in pairs {
+            let slot = match key.to_string().as_str() {
+                "year" => &mut overrides.year,
+                "day" => &mut overrides.day,
+                _ => return Err(Error::new_spanned(key, "expected `year` or `day`")),
+            };
+            if slot.is_some() {
+                return Err(Error::new_spanned(key.clone(), format!("`{}` is given more than once", key)));
+            }
+            *slot = Some(value);
+        }
+        Ok(overrides)
+    }
+}
+
+fn env_var(name: &str) -> syn::Result<String> {
+    env::var(name).map_err(|_| Error::new(Span::call_site(), format!("{} not set in environment", name)))
+}
+
+fn resolve_year(overrides: &Overrides) -> syn::Result<u32> {
+    if let Some(year) = &overrides.year {
+        return parse_year(year.base10_digits()).ok_or_else(|| Error::new_spanned(
+            year, format!("not a year of Advent of Code, which started in {}", FIRST_YEAR)));
+    }
+    if let Some(year) = env::var("CARGO_BIN_NAME").ok().and_then(|name| parse_bin_name(&name)?.year) {
+        return Ok(year);
+    }
+    let pkg_name = env_var("CARGO_PKG_NAME")?;
+    parse_package_name(&pkg_name).ok_or_else(|| Error::new(Span::call_site(), format!(
+        "no year found in package name {}; include it (e.g. `aoc{}`), or pass `year = YYYY`",
+        pkg_name, FIRST_YEAR)))
+}
+
+pub fn year(input: TokenStream) -> syn::Result<TokenStream> {
+    let year = resolve_year(&syn::parse2(input)?)?;
+    Ok(quote!(#year))
+}
+
+pub fn day(input: TokenStream) -> syn::Result<TokenStream> {
+    let overrides: Overrides = syn::parse2(input)?;
+    let (day, span) = match &overrides.day {
+        Some(day) => (day.base10_parse::<u32>()?, day.span()),
+        None => {
+            let bin_name = env_var("CARGO_BIN_NAME")?;
+            let parsed = parse_bin_name(&bin_name).ok_or_else(|| Error::new(Span::call_site(), format!(
+                "no day found in binary name {}; name it like `01.rs`, `day1.rs` or `2024_01.rs`, or \
+                pass `day = N`", bin_name)))?;
+            (parsed.day, Span::call_site())
+        }
+    };
+    // If the year cannot be determined, `year!` reports that, so check against the longest event.
+    let (year, max_day) = match resolve_year(&overrides) {
+        Ok(year) => (year.to_string(), num_days(year)),
+        Err(_) => ("the event".to_owned(), num_days(FIRST_YEAR)),
+    };
+    if !(1..=max_day).contains(&day) {
+        return Err(Error::new(span, format!("day {} is out of range: {} has days 1 to {}", day, year, max_day)));
+    }
+    Ok(quote!(#day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides() {
+        let overrides: Overrides = syn::parse2(quote!(day = 3, year = 2024)).unwrap();
+        assert_eq!(overrides.day.as_ref().unwrap().base10_digits(), "3");
+        assert_eq!(resolve_year(&overrides).unwrap(), 2024);
+        assert!(syn::parse2::<Overrides>(quote!(month = 3)).is_err());
+        assert!(syn::parse2::<Overrides>(quote!(day = 3, day = 4)).is_err());
+    }
+}
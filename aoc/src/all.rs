@@ -0,0 +1,245 @@
+use std::env;
+use std::error::Error;
+use std::io::Read;
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::paths::day_binaries;
+use crate::record::{TIMEOUT_ERROR, TSV_HEADER};
+use crate::runner::{EXIT_FAILURE, EXIT_USAGE};
+use crate::table::format_table;
+use crate::{input_file_name, set_manifest_dir, set_package_name, FormatDuration, OutputFormat, Record};
+
+const USAGE: &str = "\
+Usage: all [OPTIONS]
+
+Runs every day's binary on its puzzle input and prints a summary table.
+
+Options:
+  --format FORMAT    Print a table (text, the default), or one record per day as json or tsv
+  --timeout S        Stop each day that takes longer than S seconds, and report it as TIMEOUT
+  --skip-missing     Skip days whose input has not been fetched yet, instead of fetching it
+  --no-build         Do not run `cargo build` first
+  -h, --help         Print this help and exit";
+
+/// How long a day may run past its `--timeout` before it is killed, in case it hangs somewhere its
+/// own watchdog does not cover, e.g. while fetching its input.
+const TIMEOUT_GRACE: Duration = Duration::from_secs(2);
+
+/// Generates a `fn main()` that runs all days of the calendar. Put this in `src/bin/all.rs`.
+#[macro_export]
+macro_rules! all {
+    () => {
+        fn main() {
+            $crate::run_all($crate::year!(), env!("CARGO_MANIFEST_DIR"), env!("CARGO_PKG_NAME"));
+        }
+    }
+}
+
+/// One row of the summary table.
+struct Row {
+    day: u32,
+    /// The results reported by the day's binary, if it was run successfully.
+    record: Option<Record>,
+    status: String,
+}
+
+/// Runs each day's binary (e.g. `src/bin/01.rs`) as a subprocess, and prints a table of the results.
+/// Exits with a nonzero status if any day failed or had a changed answer.
+pub fn run_all(year: u32, manifest_dir: &str, package_name: &str) {
+    let mut skip_missing = false;
+    let mut build = true;
+    let mut format = OutputFormat::Text;
+    let mut timeout = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => match args.next().as_deref().and_then(OutputFormat::parse) {
+                Some(value) => format = value,
+                None => {
+                    eprintln!("--format requires text, json or tsv\n\n{}", USAGE);
+                    process::exit(EXIT_USAGE);
+                }
+            },
+            "--timeout" => match args.next().and_then(|secs| Duration::try_from_secs_f64(secs.parse().ok()?).ok()) {
+                Some(budget) => timeout = Some(budget),
+                None => {
+                    eprintln!("--timeout requires a number of seconds\n\n{}", USAGE);
+                    process::exit(EXIT_USAGE);
+                }
+            },
+            "--skip-missing" => skip_missing = true,
+            "--no-build" => build = false,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => {
+                eprintln!("unexpected argument {}\n\n{}", arg, USAGE);
+                process::exit(EXIT_USAGE);
+            }
+        }
+    }
+
+    set_manifest_dir(manifest_dir);
+    set_package_name(package_name);
+    let manifest_dir = Path::new(manifest_dir);
+    if build {
+        if let Err(err) = build_bins(manifest_dir) {
+            eprintln!("Failed to build binaries: {}", err);
+            process::exit(EXIT_FAILURE);
+        }
+    }
+
+    let binaries = day_binaries(year).unwrap_or_else(|err| {
+        eprintln!("Failed to find days: {}", err);
+        process::exit(EXIT_FAILURE);
+    });
+    let mut rows = Vec::new();
+    for (day, binary) in binaries {
+        let row = if skip_missing && !input_file_name(year, day).exists() {
+            Row { day, record: None, status: "SKIPPED".to_owned() }
+        } else {
+            eprintln!("Running day {}...", day);
+            match run_day(manifest_dir, year, day, &binary, timeout) {
+                Ok(record) => Row { day, status: status(&record).to_owned(), record: Some(record) },
+                Err(err) => Row { day, record: None, status: format!("FAILED: {}", err) },
+            }
+        };
+        rows.push(row);
+    }
+
+    match format {
+        OutputFormat::Text => print_table(year, &rows),
+        OutputFormat::Json => {
+            for record in records(year, &rows) {
+                println!("{}", record.to_json());
+            }
+        }
+        OutputFormat::Tsv => {
+            println!("{}", TSV_HEADER);
+            for record in records(year, &rows) {
+                println!("{}", record.to_tsv());
+            }
+        }
+    }
+    if rows.iter().any(|row| row.status != "OK" && row.status != "NEW" && row.status != "SKIPPED") {
+        process::exit(EXIT_FAILURE);
+    }
+}
+
+fn build_bins(manifest_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let mut command = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned()));
+    command.current_dir(manifest_dir).args(["build", "--bins", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    let status = command.status()?;
+    if !status.success() {
+        return Err(format!("cargo exited with {}", status).into());
+    }
+    Ok(())
+}
+
+/// Runs a day's binary, which is expected to be next to the current executable, and parses its TSV
+/// output. The timeout is passed on to the binary; if it is still running [`TIMEOUT_GRACE`] after
+/// that, it is killed and reported as timed out.
+fn run_day(manifest_dir: &Path, year: u32, day: u32, binary: &str, timeout: Option<Duration>) -> Result<Record, Box<dyn Error>> {
+    let exe = env::current_exe()?.with_file_name(format!("{}{}", binary, env::consts::EXE_SUFFIX));
+    let mut command = Command::new(&exe);
+    command.args(["--format", "tsv"]).current_dir(manifest_dir).stdout(Stdio::piped()).stderr(Stdio::null());
+    if let Some(budget) = timeout {
+        command.args(["--timeout", &budget.as_secs_f64().to_string()]);
+    }
+    let start = Instant::now();
+    let mut child = command.spawn().map_err(|err| format!("could not run {}: {}", exe.display(), err))?;
+    let mut stdout = child.stdout.take().ok_or("no output")?;
+    let reader = thread::spawn(move || {
+        let mut output = String::new();
+        stdout.read_to_string(&mut output).map(|_| output)
+    });
+
+    let deadline = timeout.map(|budget| start + budget + TIMEOUT_GRACE);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            child.kill()?;
+            child.wait()?;
+            let elapsed = start.elapsed();
+            return Ok(Record {
+                year,
+                day,
+                stages: vec![("run", elapsed)],
+                error: Some(("run".to_owned(), format!("{} {}", TIMEOUT_ERROR, FormatDuration(elapsed)))),
+                ..Record::default()
+            });
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    let stdout = reader.join().map_err(|_| "failed to read the output")??;
+    let line = stdout.lines().last().filter(|line| *line != TSV_HEADER).unwrap_or_default();
+    Ok(Record::from_tsv(line).map_err(|_| format!("exited with {}", status))?)
+}
+
+/// Summarizes a day's result in a single word.
+fn status(record: &Record) -> &'static str {
+    if record.is_timeout() {
+        "TIMEOUT"
+    } else if record.error.is_some() {
+        "ERROR"
+    } else if record.checks.contains(&Some("CHANGED")) {
+        "CHANGED"
+    } else if record.checks.contains(&Some("NEW")) {
+        "NEW"
+    } else {
+        "OK"
+    }
+}
+
+/// Returns a record for each day that was not skipped. Days whose binary failed get a record with
+/// just the error.
+fn records(year: u32, rows: &[Row]) -> Vec<Record> {
+    rows.iter()
+        .filter(|row| row.status != "SKIPPED")
+        .map(|row| row.record.clone().unwrap_or_else(|| Record {
+            year,
+            day: row.day,
+            error: Some(("run".to_owned(), row.status.clone())),
+            ..Record::default()
+        }))
+        .collect()
+}
+
+fn print_table(year: u32, rows: &[Row]) {
+    let header = ["Day", "Part 1", "Part 2", "Time", "Check"].map(str::to_owned);
+    let mut lines = vec![header];
+    for row in rows {
+        let part = |index: usize| {
+            let answer = row.record.as_ref().and_then(|record| record.answers.get(index).cloned().flatten());
+            answer.map_or(String::new(), |answer| answer.trim().replace('\n', "⏎"))
+        };
+        let duration = row.record.as_ref().map(Record::total_duration);
+        lines.push([
+            row.day.to_string(),
+            part(0),
+            part(1),
+            duration.map_or(String::new(), |duration| FormatDuration(duration).to_string()),
+            row.status.clone(),
+        ]);
+    }
+    let total = rows.iter().filter_map(|row| row.record.as_ref()).map(Record::total_duration).sum::<Duration>();
+    lines.push([
+        "Total".to_owned(),
+        String::new(),
+        String::new(),
+        FormatDuration(total).to_string(),
+        String::new(),
+    ]);
+
+    println!("Advent of Code {}", year);
+    print!("{}", format_table(&lines, &[1, lines.len() - 1]));
+}
@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+
+use crate::{answers_file_name, ensure_dir_exists};
+
+/// The result of comparing an answer to the known (accepted) answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    /// Same as the known answer.
+    Ok,
+    /// Different from the known answer, which is included.
+    Changed(String),
+    /// No known answer yet.
+    New,
+}
+
+impl Check {
+    /// Returns a short status string, without the known answer.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Check::Ok => "OK",
+            Check::Changed(_) => "CHANGED",
+            Check::New => "NEW",
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Check::Ok => write!(f, "OK"),
+            Check::Changed(known) => write!(f, "CHANGED, was {}", known),
+            Check::New => write!(f, "NEW"),
+        }
+    }
+}
+
+/// The known answers to a day's puzzle, indexed by 1-based part number.
+///
+/// They are stored in a file with one line per part, consisting of the part number, a tab, and
+/// the answer. Newlines, carriage returns, tabs and backslashes in the answer are escaped as `\n`,
+/// `\r`, `\t` and `\\`, so that multi-line answers still fit on one line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KnownAnswers {
+    parts: BTreeMap<usize, String>,
+}
+
+impl KnownAnswers {
+    pub fn load(year: u32, day: u32) -> Self {
+        fs::read_to_string(answers_file_name(year, day))
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, year: u32, day: u32) -> Result<(), Box<dyn Error>> {
+        let file_name = answers_file_name(year, day);
+        ensure_dir_exists(&file_name)?;
+        fs::write(&file_name, self.to_string())?;
+        Ok(())
+    }
+
+    fn parse(contents: &str) -> Self {
+        let parts = contents
+            .lines()
+            .filter_map(|line| {
+                let (part, answer) = line.split_once('\t')?;
+                Some((part.parse().ok()?, unescape(answer)))
+            })
+            .collect();
+        Self { parts }
+    }
+
+    pub fn get(&self, part: usize) -> Option<&str> {
+        self.parts.get(&part).map(String::as_str)
+    }
+
+    pub fn set(&mut self, part: usize, answer: String) {
+        self.parts.insert(part, answer);
+    }
+
+    pub fn check(&self, part: usize, answer: &str) -> Check {
+        match self.get(part) {
+            Some(known) if known == answer => Check::Ok,
+            Some(known) => Check::Changed(known.to_owned()),
+            None => Check::New,
+        }
+    }
+}
+
+impl fmt::Display for KnownAnswers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (part, answer) in &self.parts {
+            writeln!(f, "{}\t{}", part, escape(answer))?;
+        }
+        Ok(())
+    }
+}
+
+/// Escapes backslashes, newlines, carriage returns and tabs, so that the text fits in one field of
+/// a line of tab-separated values.
+pub(crate) fn escape(answer: &str) -> String {
+    let mut out = String::with_capacity(answer.len());
+    for c in answer.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+pub(crate) fn unescape(answer: &str) -> String {
+    let mut out = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        let unescaped = match (c, chars.clone().next()) {
+            ('\\', Some('n')) => '\n',
+            ('\\', Some('r')) => '\r',
+            ('\\', Some('t')) => '\t',
+            ('\\', Some('\\')) => '\\',
+            _ => {
+                out.push(c);
+                continue;
+            }
+        };
+        out.push(unescaped);
+        chars.next();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let mut known = KnownAnswers::default();
+        known.set(1, "1234".to_owned());
+        known.set(2, "#..#\n\\..#".to_owned());
+        assert_eq!(known.to_string(), "1\t1234\n2\t#..#\\n\\\\..#\n");
+        assert_eq!(KnownAnswers::parse(&known.to_string()), known);
+    }
+
+    #[test]
+    fn escapes() {
+        let text = "a\tb\r\nc\\t";
+        assert_eq!(escape(text), "a\\tb\\r\\nc\\\\t");
+        assert!(!escape(text).contains(['\t', '\r', '\n']));
+        assert_eq!(unescape(&escape(text)), text);
+    }
+
+    #[test]
+    fn check() {
+        let known = KnownAnswers::parse("1\t1234\n");
+        assert_eq!(known.check(1, "1234"), Check::Ok);
+        assert_eq!(known.check(1, "1235"), Check::Changed("1234".to_owned()));
+        assert_eq!(known.check(2, "1234"), Check::New);
+    }
+}
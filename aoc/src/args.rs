@@ -0,0 +1,301 @@
+use std::error::Error;
+use std::fs;
+use std::io::Read;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::{try_example, try_input, OutputFormat};
+
+pub const USAGE: &str = "\
+Usage: <day binary> [OPTIONS] [-]
+
+Options:
+  -e, --example N    Run on example N instead of the puzzle input
+  -i, --input PATH   Run on the contents of the file at PATH
+  -                  Run on input read from stdin
+  -p, --part 1|2     Only print the answer to the given part
+      --submit 1|2   Submit the answer to the given part to the site
+      --accept       Save the answers as the known answers to check against
+      --bench        Run repeatedly and print timing statistics
+      --bench-time S Spend S seconds benchmarking (default 3; implies --bench)
+      --bench-out PATH
+                     Append benchmark results to the TSV file PATH (implies --bench)
+      --timeout S    Give up if the solution takes longer than S seconds, and exit with status 5
+      --format FORMAT
+                     Print results as text (default), json or tsv, e.g. for scripts
+      --puzzle       Print the puzzle description instead of running the solution
+      --markdown     Print the puzzle description as Markdown (implies --puzzle)
+      --refresh-examples
+                     Fetch the puzzle page again and save any new examples first
+  -h, --help         Print this help and exit";
+
+const DEFAULT_BENCH_TIME: Duration = Duration::from_secs(3);
+
+/// Where the runner should get its input from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// The user's puzzle input, fetched and cached if needed.
+    Input,
+    /// One of the examples from the puzzle description, fetched and cached if needed.
+    Example(usize),
+    /// An arbitrary file.
+    File(PathBuf),
+    /// Standard input.
+    Stdin,
+}
+
+impl Source {
+    pub fn read(&self, year: u32, day: u32) -> Result<String, Box<dyn Error>> {
+        match self {
+            Source::Input => try_input(year, day),
+            Source::Example(index) => try_example(year, day, *index),
+            Source::File(path) => Ok(fs::read_to_string(path)?),
+            Source::Stdin => {
+                let mut contents = String::new();
+                io::stdin().lock().read_to_string(&mut contents)?;
+                Ok(contents)
+            }
+        }
+    }
+
+    /// Returns a human-readable description to put after "Answer to YYYY day DD".
+    pub fn describe(&self) -> String {
+        match self {
+            Source::Input => String::new(),
+            Source::Example(index) => format!(" example {}", index),
+            Source::File(path) => format!(" input {}", path.display()),
+            Source::Stdin => " input from stdin".to_owned(),
+        }
+    }
+}
+
+/// Command-line options accepted by the `fn main()` generated by `aoc::main!`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub source: Source,
+    /// If set, only this part (1-based) is printed.
+    pub part: Option<usize>,
+    /// If set, the answer to this part (1-based) is submitted.
+    pub submit: Option<usize>,
+    /// Whether to save the answers as the known answers.
+    pub accept: bool,
+    /// If set, benchmark the solution for this long.
+    pub bench: Option<Duration>,
+    /// If set, benchmark results are appended to this file.
+    pub bench_out: Option<PathBuf>,
+    /// If set, the process exits if the solution takes longer than this.
+    pub timeout: Option<Duration>,
+    /// How to print the results.
+    pub format: OutputFormat,
+    /// Whether to print the puzzle description instead of running the solution.
+    pub puzzle: bool,
+    /// Whether to print the puzzle description as Markdown rather than terminal text.
+    pub markdown: bool,
+    /// Whether to fetch the examples again before running, even if they are all present.
+    pub refresh_examples: bool,
+    pub help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            source: Source::Input,
+            part: None,
+            submit: None,
+            accept: false,
+            bench: None,
+            bench_out: None,
+            timeout: None,
+            format: OutputFormat::Text,
+            puzzle: false,
+            markdown: false,
+            refresh_examples: false,
+            help: false,
+        }
+    }
+}
+
+impl Options {
+    /// Parses the given arguments, not including the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut source_set = false;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut source = None;
+            match arg.as_str() {
+                "-e" | "--example" => {
+                    source = Some(Source::Example(parse_value(&arg, args.next())?));
+                }
+                "-i" | "--input" => {
+                    source = Some(Source::File(parse_value(&arg, args.next())?));
+                }
+                "-" => {
+                    source = Some(Source::Stdin);
+                }
+                "-p" | "--part" => {
+                    options.part = Some(parse_part(&arg, args.next())?);
+                }
+                "--submit" => {
+                    options.submit = Some(parse_part(&arg, args.next())?);
+                }
+                "--accept" => {
+                    options.accept = true;
+                }
+                "--bench" => {
+                    options.bench.get_or_insert(DEFAULT_BENCH_TIME);
+                }
+                "--bench-time" => {
+                    let secs: f64 = parse_value(&arg, args.next())?;
+                    let budget = Duration::try_from_secs_f64(secs)
+                        .map_err(|_| format!("invalid value for {}: {}", arg, secs))?;
+                    options.bench = Some(budget);
+                }
+                "--bench-out" => {
+                    options.bench_out = Some(parse_value(&arg, args.next())?);
+                    options.bench.get_or_insert(DEFAULT_BENCH_TIME);
+                }
+                "--timeout" => {
+                    let secs: f64 = parse_value(&arg, args.next())?;
+                    let timeout = Duration::try_from_secs_f64(secs)
+                        .map_err(|_| format!("invalid value for {}: {}", arg, secs))?;
+                    options.timeout = Some(timeout);
+                }
+                "--format" => {
+                    let value: String = parse_value(&arg, args.next())?;
+                    options.format = OutputFormat::parse(&value)
+                        .ok_or_else(|| format!("invalid value for {}: {}", arg, value))?;
+                }
+                "--puzzle" => {
+                    options.puzzle = true;
+                }
+                "--markdown" => {
+                    options.puzzle = true;
+                    options.markdown = true;
+                }
+                "--refresh-examples" => {
+                    options.refresh_examples = true;
+                }
+                "-h" | "--help" => {
+                    options.help = true;
+                }
+                _ => {
+                    return Err(format!("unexpected argument {}", arg));
+                }
+            }
+            if let Some(source) = source {
+                if source_set {
+                    return Err("at most one of --example, --input and - may be given".to_owned());
+                }
+                options.source = source;
+                source_set = true;
+            }
+        }
+        if options.submit.is_some() && options.source != Source::Input {
+            return Err("--submit can only be used with the puzzle input".to_owned());
+        }
+        if options.accept && options.source != Source::Input {
+            return Err("--accept can only be used with the puzzle input".to_owned());
+        }
+        Ok(options)
+    }
+
+    /// Returns whether the given part (1-based) should be printed.
+    pub fn includes_part(&self, part: usize) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+fn parse_part(arg: &str, value: Option<String>) -> Result<usize, String> {
+    let part = parse_value(arg, value)?;
+    if !(1..=2).contains(&part) {
+        return Err(format!("part must be 1 or 2, got {}", part));
+    }
+    Ok(part)
+}
+
+fn parse_value<T: std::str::FromStr>(arg: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", arg))?;
+    value.parse().map_err(|_| format!("invalid value for {}: {}", arg, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(parse(&[]), Ok(Options::default()));
+    }
+
+    #[test]
+    fn sources() {
+        assert_eq!(parse(&["--example", "2"]).unwrap().source, Source::Example(2));
+        assert_eq!(parse(&["-i", "foo.txt"]).unwrap().source, Source::File("foo.txt".into()));
+        assert_eq!(parse(&["-"]).unwrap().source, Source::Stdin);
+        assert!(parse(&["-e", "0", "-"]).is_err());
+        assert!(parse(&["-e", "zero"]).is_err());
+        assert!(parse(&["-e"]).is_err());
+    }
+
+    #[test]
+    fn part() {
+        assert_eq!(parse(&["--part", "2"]).unwrap().part, Some(2));
+        assert!(parse(&["--part", "3"]).is_err());
+    }
+
+    #[test]
+    fn submit() {
+        assert_eq!(parse(&["--submit", "1"]).unwrap().submit, Some(1));
+        assert!(parse(&["--submit", "0"]).is_err());
+        assert!(parse(&["--submit", "1", "-e", "0"]).is_err());
+    }
+
+    #[test]
+    fn accept() {
+        assert!(parse(&["--accept"]).unwrap().accept);
+        assert!(parse(&["--accept", "-"]).is_err());
+    }
+
+    #[test]
+    fn bench() {
+        assert_eq!(parse(&["--bench"]).unwrap().bench, Some(Duration::from_secs(3)));
+        assert_eq!(parse(&["--bench-time", "0.5"]).unwrap().bench, Some(Duration::from_millis(500)));
+        assert_eq!(parse(&["--bench-time", "0.5", "--bench"]).unwrap().bench, Some(Duration::from_millis(500)));
+        assert!(parse(&["--bench-time", "-1"]).is_err());
+        let options = parse(&["--bench-out", "bench.tsv"]).unwrap();
+        assert_eq!(options.bench, Some(Duration::from_secs(3)));
+        assert_eq!(options.bench_out, Some("bench.tsv".into()));
+    }
+
+    #[test]
+    fn timeout() {
+        assert_eq!(parse(&["--timeout", "2.5"]).unwrap().timeout, Some(Duration::from_millis(2500)));
+        assert!(parse(&["--timeout", "soon"]).is_err());
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(parse(&["--format", "json"]).unwrap().format, OutputFormat::Json);
+        assert_eq!(parse(&["--format", "tsv"]).unwrap().format, OutputFormat::Tsv);
+        assert!(parse(&["--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn puzzle() {
+        let options = parse(&["--puzzle"]).unwrap();
+        assert!(options.puzzle && !options.markdown);
+        let options = parse(&["--markdown"]).unwrap();
+        assert!(options.puzzle && options.markdown);
+    }
+
+    #[test]
+    fn refresh_examples() {
+        assert!(parse(&["--refresh-examples", "-e", "1"]).unwrap().refresh_examples);
+    }
+}
@@ -0,0 +1,131 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Minimum number of measured runs, even if they exceed the time budget.
+const MIN_RUNS: usize = 3;
+
+/// Statistics over the durations of a number of runs.
+#[derive(Clone, Debug)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty());
+        samples.sort_unstable();
+        let runs = samples.len();
+        let nanos = samples.iter().map(|d| d.as_nanos() as f64).collect::<Vec<_>>();
+        let mean = nanos.iter().sum::<f64>() / runs as f64;
+        let variance = if runs > 1 {
+            nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / (runs - 1) as f64
+        } else {
+            0.0
+        };
+        let median = if runs.is_multiple_of(2) {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+        Self {
+            runs,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean / 1e9),
+            std_dev: Duration::from_secs_f64(variance.sqrt() / 1e9),
+        }
+    }
+
+    /// Appends the statistics as a tab-separated line to the given file, writing a header line
+    /// first if the file is new.
+    pub fn append_to_file(&self, path: &Path, year: u32, day: u32) -> Result<(), Box<dyn Error>> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if file.metadata()?.len() == 0 {
+            writeln!(file, "year\tday\truns\tmin_ns\tmedian_ns\tmean_ns\tstd_dev_ns")?;
+        }
+        writeln!(
+            file, "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            year, day, self.runs, self.min.as_nanos(), self.median.as_nanos(), self.mean.as_nanos(),
+            self.std_dev.as_nanos())?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f, "{} runs: min {}, median {}, mean {} ± {}",
+            self.runs, FormatDuration(self.min), FormatDuration(self.median),
+            FormatDuration(self.mean), FormatDuration(self.std_dev))
+    }
+}
+
+/// Formats a duration with a unit that suits its magnitude, with nanosecond precision.
+pub struct FormatDuration(pub Duration);
+
+impl fmt::Display for FormatDuration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nanos = self.0.as_nanos() as f64;
+        if nanos < 1e3 {
+            write!(f, "{:.0} ns", nanos)
+        } else if nanos < 1e6 {
+            write!(f, "{:.3} µs", nanos / 1e3)
+        } else if nanos < 1e9 {
+            write!(f, "{:.6} ms", nanos / 1e6)
+        } else {
+            write!(f, "{:.9} s", nanos / 1e9)
+        }
+    }
+}
+
+/// Runs `f` repeatedly: first for a warm-up period of a tenth of the budget, then measuring each
+/// run until the budget is used up.
+pub fn bench(budget: Duration, mut f: impl FnMut()) -> Stats {
+    let warm_up_end = Instant::now() + budget / 10;
+    loop {
+        f();
+        if Instant::now() >= warm_up_end {
+            break;
+        }
+    }
+
+    let end = Instant::now() + budget;
+    let mut samples = Vec::new();
+    while samples.len() < MIN_RUNS || Instant::now() < end {
+        let start = Instant::now();
+        f();
+        samples.push(start.elapsed());
+    }
+    Stats::from_samples(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_samples([4, 1, 3, 2].map(Duration::from_micros).to_vec());
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.mean, Duration::from_nanos(2500));
+        assert_eq!(stats.std_dev.as_nanos(), 1291);
+    }
+
+    #[test]
+    fn format_duration() {
+        assert_eq!(FormatDuration(Duration::from_nanos(123)).to_string(), "123 ns");
+        assert_eq!(FormatDuration(Duration::from_nanos(12_345)).to_string(), "12.345 µs");
+        assert_eq!(FormatDuration(Duration::from_nanos(1_234_567)).to_string(), "1.234567 ms");
+        assert_eq!(FormatDuration(Duration::from_nanos(2_000_000_001)).to_string(), "2.000000001 s");
+    }
+}
@@ -0,0 +1,74 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub use crate::names::num_days;
+
+/// Time of day (in UTC) at which puzzles unlock: midnight US Eastern Standard Time.
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+/// Returns the moment the puzzle for the given day unlocks.
+pub fn unlock_time(year: u32, day: u32) -> SystemTime {
+    let days = days_since_epoch(year, 12, day);
+    UNIX_EPOCH + Duration::from_secs(days * 24 * 60 * 60 + UNLOCK_HOUR_UTC * 60 * 60)
+}
+
+/// Returns whether the puzzle for the given day has unlocked at the given time.
+pub fn is_unlocked(year: u32, day: u32, now: SystemTime) -> bool {
+    unlock_time(year, day) <= now
+}
+
+/// Returns the number of days from 1970-01-01 to the given date in the proleptic Gregorian
+/// calendar. Uses the algorithm from Howard Hinnant's `days_from_civil`.
+fn days_since_epoch(year: u32, month: u32, day: u32) -> u64 {
+    let year = u64::from(if month <= 2 { year - 1 } else { year });
+    let month = u64::from(month);
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + u64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Formats a duration as a countdown, e.g. `1d 02:03:04` or `02:03:04`.
+pub fn format_countdown(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes, seconds) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{}d {:02}:{:02}:{:02}", days, hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unix_secs(time: SystemTime) -> u64 {
+        time.duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    #[test]
+    fn unlock_times() {
+        assert_eq!(unix_secs(unlock_time(2025, 1)), 1764565200);
+        assert_eq!(unix_secs(unlock_time(2024, 25)), 1735102800);
+    }
+
+    #[test]
+    fn unlocked() {
+        let unlock = unlock_time(2025, 3);
+        assert!(!is_unlocked(2025, 3, unlock - Duration::from_secs(1)));
+        assert!(is_unlocked(2025, 3, unlock));
+    }
+
+    #[test]
+    fn days() {
+        assert_eq!(num_days(2025), 12);
+        assert_eq!(num_days(2015), 25);
+    }
+
+    #[test]
+    fn countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(format_countdown(Duration::from_secs(90061)), "1d 01:01:01");
+    }
+}
@@ -0,0 +1,68 @@
+//! Lets the macros report the cause chain of any `std::error::Error` that a solution returns. The
+//! runner only knows that an error is `Display`, so where the concrete type is known, the macros
+//! wrap each function to format errors that implement `Error` with their causes first (see
+//! [`__chain_errors!`](crate::__chain_errors)). Other values pass through unchanged.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::format_error;
+
+/// An error that has been formatted with its chain of causes.
+#[derive(Debug)]
+pub struct Chained(String);
+
+impl fmt::Display for Chained {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Selected for a `Result` whose error implements `Error`.
+pub struct ChainTag;
+
+impl ChainTag {
+    pub fn wrap<T, E: Error + 'static>(self, result: Result<T, E>) -> Result<T, Chained> {
+        result.map_err(|err| Chained(format_error(&err)))
+    }
+}
+
+/// Selected for everything else.
+pub struct PlainTag;
+
+impl PlainTag {
+    pub fn wrap<A>(self, value: A) -> A {
+        value
+    }
+}
+
+/// Called as `(&value).__aoc_error_kind()`, this matches without autoref, so it takes precedence
+/// over [`PlainKind`] when it applies.
+pub trait ChainKind {
+    fn __aoc_error_kind(&self) -> ChainTag {
+        ChainTag
+    }
+}
+
+impl<T, E: Error + 'static> ChainKind for Result<T, E> {}
+
+pub trait PlainKind {
+    fn __aoc_error_kind(&self) -> PlainTag {
+        PlainTag
+    }
+}
+
+impl<A> PlainKind for &A {}
+
+/// Evaluates to the value, except that a `Result` whose error implements `Error` gets a [`Chained`]
+/// error instead, which includes the causes.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __chain_errors {
+    ($value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::chain::{ChainKind as _, PlainKind as _};
+        let value = $value;
+        (&value).__aoc_error_kind().wrap(value)
+    }};
+}
@@ -0,0 +1,168 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use reqwest::blocking::{Client, ClientBuilder};
+use reqwest::StatusCode;
+
+use crate::session::{invalid_session_error, is_logged_out_page, load_session_cookie};
+use crate::Config;
+
+/// Identifies this client to the site, as its automation guidelines ask: a link to the code and a
+/// way to contact the author.
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),
+    " (", env!("CARGO_PKG_REPOSITORY"), "; ", env!("CARGO_PKG_AUTHORS"), ")");
+
+/// Minimum time between two requests, so that bulk operations do not hammer the site.
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(2);
+
+static LAST_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Sends an authenticated GET request for the given path (e.g. `/2025/day/1/input`) to the
+/// configured site, and returns the response body.
+pub(crate) fn send_get_request(path: &str) -> Result<String, Box<dyn Error>> {
+    send_request(&Config::load(), path, None)
+}
+
+/// Sends an authenticated POST request with a form body for the given path to the configured
+/// site, and returns the response body.
+pub(crate) fn send_post_request(path: &str, form: &[(&str, &str)]) -> Result<String, Box<dyn Error>> {
+    send_request(&Config::load(), path, Some(form))
+}
+
+fn send_request(config: &Config, path: &str, form: Option<&[(&str, &str)]>) -> Result<String, Box<dyn Error>> {
+    let url = config.url(path);
+    if config.offline {
+        return Err(format!("offline mode is enabled (AOC_OFFLINE), not requesting {}", url).into());
+    }
+    let session_cookie = load_session_cookie(config)?;
+    throttle();
+    send_authenticated(&url, form, &session_cookie)
+}
+
+/// Sleeps until at least `MIN_REQUEST_INTERVAL` has passed since the previous request.
+fn throttle() {
+    let mut last_request = LAST_REQUEST.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(elapsed) = last_request.map(|last_request| last_request.elapsed()) {
+        if elapsed < MIN_REQUEST_INTERVAL {
+            thread::sleep(MIN_REQUEST_INTERVAL - elapsed);
+        }
+    }
+    *last_request = Some(Instant::now());
+}
+
+/// Sends the request and returns the body. The site responds with HTTP 400 (for inputs) or a
+/// logged-out page (for everything else) if the session cookie is invalid; both are reported as an
+/// error.
+fn send_authenticated(url: &str, form: Option<&[(&str, &str)]>, session_cookie: &str) -> Result<String, Box<dyn Error>> {
+    let client = build_client()?;
+    let request = match form {
+        Some(form) => client.post(url).form(form),
+        None => client.get(url),
+    };
+    let response = request
+        .header(reqwest::header::COOKIE, format!("session={}", session_cookie))
+        .send()?;
+    if response.status() == StatusCode::BAD_REQUEST {
+        return Err(invalid_session_error());
+    }
+    let body = response.error_for_status()?.text().map_err(ReadError)?;
+    if is_logged_out_page(&body) {
+        return Err(invalid_session_error());
+    }
+    Ok(body)
+}
+
+/// Reading the response body failed, after the server had already received the request and may
+/// have acted on it.
+#[derive(Debug)]
+pub(crate) struct ReadError(reqwest::Error);
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "failed to read the response: {}", self.0)
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+fn build_client() -> Result<Client, reqwest::Error> {
+    ClientBuilder::new()
+        .user_agent(USER_AGENT)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Starts a stand-in server that answers a single request with the given status line and body,
+    /// and returns its base URL and a handle that yields the raw request.
+    fn serve_once(status: &'static str, body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 4096];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            write!(stream, "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body).unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn user_agent() {
+        assert!(USER_AGENT.starts_with("aoc/"));
+        assert!(USER_AGENT.contains("https://github.com/"));
+        assert!(USER_AGENT.contains('@'));
+    }
+
+    #[test]
+    fn get_from_stand_in_server() {
+        let (base_url, handle) = serve_once("200 OK", "1,2,3\n");
+        let config = Config { base_url, ..Config::default() };
+        let body = send_authenticated(&config.url("/2025/day/1/input"), None, "s3cr3t").unwrap();
+        assert_eq!(body, "1,2,3\n");
+
+        let request = handle.join().unwrap().to_lowercase();
+        assert!(request.starts_with("get /2025/day/1/input http/1.1\r\n"));
+        assert!(request.contains("cookie: session=s3cr3t\r\n"));
+        assert!(request.contains(&format!("user-agent: {}\r\n", USER_AGENT.to_lowercase())));
+    }
+
+    #[test]
+    fn invalid_session() {
+        let (base_url, handle) = serve_once("400 Bad Request", "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n");
+        let err = send_authenticated(&format!("{}/2025/day/1/input", base_url), None, "expired").unwrap_err();
+        assert!(err.to_string().contains("session cookie is invalid or expired"));
+        handle.join().unwrap();
+
+        let (base_url, handle) = serve_once("200 OK", r#"<html><a href="/2025/auth/login">[Log In]</a></html>"#);
+        let err = send_authenticated(&format!("{}/2025/day/1", base_url), None, "expired").unwrap_err();
+        assert!(err.to_string().contains("session cookie is invalid or expired"));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn offline() {
+        let config = Config { base_url: "http://127.0.0.1:1".to_owned(), offline: true, ..Config::default() };
+        let err = send_request(&config, "/2025/day/1/input", None).unwrap_err();
+        assert!(err.to_string().contains("offline"));
+    }
+}
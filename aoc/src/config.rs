@@ -0,0 +1,143 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::paths::manifest_dir;
+
+/// Name of the optional configuration file, looked up in the manifest directory of the crate
+/// containing the solutions (or the working directory if that is unknown). It contains
+/// `key = value` lines; blank lines and lines starting with `#` are ignored.
+const CONFIG_FILE_NAME: &str = "aoc.conf";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings for talking to the Advent of Code site. Each setting can be given in the environment
+/// or in the configuration file; the environment takes precedence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Base URL of the site, without trailing slash. Environment variable `AOC_BASE_URL`, config
+    /// key `base_url`.
+    pub base_url: String,
+    /// If set, any attempt to access the network results in an error. Environment variable
+    /// `AOC_OFFLINE`, config key `offline`.
+    pub offline: bool,
+    /// Directory for inputs, examples, answers and other data files; relative paths are relative to
+    /// the manifest directory. Defaults to the manifest directory itself; files found there are
+    /// moved when they are first used. Environment variable `AOC_DATA_DIR`, config key `data_dir`.
+    pub data_dir: Option<PathBuf>,
+    /// If set, data files are stored in a directory per year (e.g. `inputs/2025/08.in`), so that
+    /// several years can share a data directory. Years other than the package's (e.g. of a binary
+    /// named `2024_03`) always get one. Environment variable `AOC_YEAR_DIRS`, config key
+    /// `year_dirs`.
+    pub year_dirs: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_owned(),
+            offline: false,
+            data_dir: None,
+            year_dirs: false,
+        }
+    }
+}
+
+impl Config {
+    /// Loads the configuration from the environment and the configuration file.
+    pub fn load() -> Self {
+        let file = fs::read_to_string(manifest_dir().join(CONFIG_FILE_NAME)).ok();
+        Self::from_sources(|name| env::var(name).ok(), file.as_deref())
+    }
+
+    fn from_sources(env: impl Fn(&str) -> Option<String>, file: Option<&str>) -> Self {
+        let file_value = |key: &str| {
+            file?.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .filter_map(|line| line.split_once('='))
+                .filter(|(k, _)| k.trim() == key)
+                .map(|(_, v)| v.trim().to_owned())
+                .next_back()
+        };
+        let value = |env_name: &str, key: &str| env(env_name).or_else(|| file_value(key));
+
+        let mut config = Config::default();
+        if let Some(base_url) = value("AOC_BASE_URL", "base_url") {
+            config.base_url = base_url.trim_end_matches('/').to_owned();
+        }
+        if let Some(offline) = value("AOC_OFFLINE", "offline") {
+            config.offline = is_true(&offline);
+        }
+        if let Some(data_dir) = value("AOC_DATA_DIR", "data_dir").filter(|data_dir| !data_dir.is_empty()) {
+            config.data_dir = Some(PathBuf::from(data_dir));
+        }
+        if let Some(year_dirs) = value("AOC_YEAR_DIRS", "year_dirs") {
+            config.year_dirs = is_true(&year_dirs);
+        }
+        config
+    }
+
+    /// Returns the directory that data files are stored in.
+    pub fn data_dir(&self) -> PathBuf {
+        self.data_dir_in(manifest_dir())
+    }
+
+    /// Returns the directory that data files are stored in, for a crate in `manifest_dir`. A
+    /// relative `data_dir` is relative to the crate.
+    pub(crate) fn data_dir_in(&self, manifest_dir: &Path) -> PathBuf {
+        match &self.data_dir {
+            Some(data_dir) => manifest_dir.join(data_dir),
+            None => manifest_dir.to_owned(),
+        }
+    }
+
+    /// Returns the full URL for the given path, which should start with a slash.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+}
+
+fn is_true(value: &str) -> bool {
+    !matches!(value.to_lowercase().as_str(), "" | "0" | "false" | "no")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(env: &[(&str, &str)], file: Option<&str>) -> Config {
+        Config::from_sources(
+            |name| env.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string()),
+            file)
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(config(&[], None), Config::default());
+        assert_eq!(config(&[], None).url("/2025/day/1"), "https://adventofcode.com/2025/day/1");
+    }
+
+    #[test]
+    fn file() {
+        let file = "# stand-in server\nbase_url = http://localhost:8080/\noffline=yes\n";
+        assert_eq!(config(&[], Some(file)), Config { base_url: "http://localhost:8080".to_owned(), offline: true, ..Config::default() });
+    }
+
+    #[test]
+    fn data_dir() {
+        let from_env = config(&[("AOC_DATA_DIR", "/data"), ("AOC_YEAR_DIRS", "true")], None);
+        assert_eq!(from_env.data_dir_in(Path::new("/crate")), PathBuf::from("/data"));
+        assert!(from_env.year_dirs);
+        let from_file = config(&[], Some("data_dir = data"));
+        assert_eq!(from_file.data_dir_in(Path::new("/crate")), PathBuf::from("/crate/data"));
+        assert_eq!(Config::default().data_dir_in(Path::new("/crate")), PathBuf::from("/crate"));
+    }
+
+    #[test]
+    fn env_overrides_file() {
+        let file = "base_url = http://localhost:8080\noffline = 1";
+        let env = [("AOC_BASE_URL", "http://127.0.0.1:1234"), ("AOC_OFFLINE", "0")];
+        assert_eq!(config(&env, Some(file)), Config { base_url: "http://127.0.0.1:1234".to_owned(), offline: false, ..Config::default() });
+    }
+}
@@ -0,0 +1,320 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+
+use kuchiki::traits::TendrilSink;
+use kuchiki::{parse_html, NodeRef};
+
+use crate::puzzle::fetch_puzzle_page;
+use crate::puzzle::is_complete;
+use crate::{ensure_dir_exists, example_answers_file_name, example_file_name, example_parts_file_name, format_error};
+
+/// Fetches the puzzle page (refreshing the cached copy) and saves each example in it as an example
+/// file, along with the part it appears in and the expected answers that can be found. Returns the
+/// number of examples.
+///
+/// This is idempotent and keeps indices stable: an example whose contents are already in an
+/// example file keeps that file's index, and new examples (e.g. from part 2, once it is unlocked)
+/// get the next free indices. Existing files are never removed or renumbered.
+pub(crate) fn fetch_examples(year: u32, day: u32) -> Result<usize, Box<dyn Error>> {
+    let root = parse_html().one(fetch_puzzle_page(year, day)?);
+    let (examples, extracted_answers) = extract(&root);
+
+    let mut existing = Vec::new();
+    while let Ok(contents) = fs::read_to_string(example_file_name(year, day, existing.len())) {
+        existing.push(contents);
+    }
+    let mut parts = ExampleParts::load(year, day);
+    let mut indices = Vec::new();
+    for example in examples {
+        let index = match existing.iter().position(|contents| *contents == example.contents) {
+            Some(index) => index,
+            None => {
+                let file_name = example_file_name(year, day, existing.len());
+                ensure_dir_exists(&file_name)?;
+                fs::write(&file_name, &example.contents)?;
+                existing.push(example.contents);
+                existing.len() - 1
+            }
+        };
+        parts.parts.entry(index).or_insert(example.part);
+        indices.push(index);
+    }
+    parts.save(year, day)?;
+
+    let mut answers = ExampleAnswers::load(year, day);
+    for (position, part, answer) in extracted_answers {
+        answers.answers.entry((indices[position], part)).or_insert(answer);
+    }
+    answers.save(year, day)?;
+
+    Ok(existing.len())
+}
+
+/// An example found on the puzzle page.
+#[derive(Debug, PartialEq, Eq)]
+struct Example {
+    /// The part (1-based) whose description contains the example.
+    part: usize,
+    contents: String,
+}
+
+/// Finds the examples on the puzzle page, and the likely expected answer to the examples for each
+/// part.
+///
+/// A `<pre>` is taken to be an example if it is the first one on the page, or if the paragraph
+/// right before it mentions an example; other `<pre>` blocks usually show intermediate states.
+///
+/// The expected answer is the last `<code><em>` in the part's `<article>`. It is linked to the last
+/// example before it, which is usually the example it belongs to; in part 2, that is often the
+/// example from part 1. Answers are returned as tuples of position in the returned examples, part
+/// number (1-based) and answer.
+fn extract(root: &NodeRef) -> (Vec<Example>, Vec<(usize, usize, String)>) {
+    let mut examples = Vec::new();
+    let mut answers = Vec::new();
+    let mut num_pres = 0;
+    for (part_index, article) in root.select("article.day-desc").into_iter().flatten().enumerate() {
+        let mut last_answer = None;
+        for node in article.as_node().descendants() {
+            let Some(element) = node.as_element() else {
+                continue;
+            };
+            match &*element.name.local {
+                "pre" => {
+                    if num_pres == 0 || follows_example_paragraph(&node) {
+                        examples.push(Example { part: part_index + 1, contents: node.text_contents() });
+                    }
+                    num_pres += 1;
+                }
+                "em" if is_code(node.parent()) && !examples.is_empty() => {
+                    last_answer = Some((examples.len() - 1, node.text_contents().trim().to_owned()));
+                }
+                _ => {}
+            }
+        }
+        if let Some((position, answer)) = last_answer {
+            answers.push((position, part_index + 1, answer));
+        }
+    }
+    (examples, answers)
+}
+
+fn follows_example_paragraph(pre: &NodeRef) -> bool {
+    pre.preceding_siblings()
+        .find(|node| node.as_element().is_some())
+        .filter(|node| node.as_element().is_some_and(|element| &*element.name.local == "p"))
+        .is_some_and(|p| p.text_contents().to_lowercase().contains("example"))
+}
+
+fn is_code(node: Option<NodeRef>) -> bool {
+    node.and_then(|node| node.as_element().map(|element| &*element.name.local == "code"))
+        .unwrap_or(false)
+}
+
+/// The part (1-based) that each example of a day was taken from, indexed by example index.
+///
+/// They are stored in a sidecar file next to the examples, with one tab-separated line per example:
+/// example index and part number.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExampleParts {
+    parts: BTreeMap<usize, usize>,
+}
+
+impl ExampleParts {
+    pub fn load(year: u32, day: u32) -> Self {
+        fs::read_to_string(example_parts_file_name(year, day))
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    fn save(&self, year: u32, day: u32) -> Result<(), Box<dyn Error>> {
+        let file_name = example_parts_file_name(year, day);
+        ensure_dir_exists(&file_name)?;
+        fs::write(&file_name, self.to_string())?;
+        Ok(())
+    }
+
+    fn parse(contents: &str) -> Self {
+        let parts = contents
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| {
+                let (index, part) = line.split_once('\t')?;
+                Some((index.trim().parse().ok()?, part.trim().parse().ok()?))
+            })
+            .collect();
+        Self { parts }
+    }
+
+    pub fn get(&self, index: usize) -> Option<usize> {
+        self.parts.get(&index).copied()
+    }
+}
+
+impl fmt::Display for ExampleParts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# Part that each example appears in: example index, part (tab-separated).")?;
+        for (index, part) in &self.parts {
+            writeln!(f, "{}\t{}", index, part)?;
+        }
+        Ok(())
+    }
+}
+
+/// Expected answers to the examples of a day, indexed by example index and part number.
+///
+/// They are stored in a sidecar file next to the examples, with one tab-separated line per answer:
+/// example index, part number and answer. The file is meant to be edited by hand if the scraper
+/// got it wrong; re-fetching only adds answers for examples and parts that are not in it yet.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExampleAnswers {
+    answers: BTreeMap<(usize, usize), String>,
+}
+
+impl ExampleAnswers {
+    pub fn load(year: u32, day: u32) -> Self {
+        fs::read_to_string(example_answers_file_name(year, day))
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    fn save(&self, year: u32, day: u32) -> Result<(), Box<dyn Error>> {
+        let file_name = example_answers_file_name(year, day);
+        ensure_dir_exists(&file_name)?;
+        fs::write(&file_name, self.to_string())?;
+        Ok(())
+    }
+
+    fn parse(contents: &str) -> Self {
+        let answers = contents
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                let index = fields.next()?.trim().parse().ok()?;
+                let part = fields.next()?.trim().parse().ok()?;
+                Some(((index, part), fields.next()?.trim().to_owned()))
+            })
+            .collect();
+        Self { answers }
+    }
+
+    pub fn get(&self, index: usize, part: usize) -> Option<&str> {
+        self.answers.get(&(index, part)).map(String::as_str)
+    }
+}
+
+impl fmt::Display for ExampleAnswers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# Expected answers to the examples: example index, part, answer (tab-separated).")?;
+        writeln!(f, "# Scraped from the puzzle description; edit by hand if they are wrong.")?;
+        for ((index, part), answer) in &self.answers {
+            writeln!(f, "{}\t{}\t{}", index, part, answer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the expected answer to the given part of the given example. If it is not known yet and
+/// the cached puzzle page does not contain both parts, the examples are fetched again first.
+/// Panics if there is no such answer.
+pub fn example_answer(year: u32, day: u32, index: usize, part: usize) -> String {
+    if ExampleAnswers::load(year, day).get(index, part).is_none() && !is_complete(year, day) {
+        eprintln!("No expected answer for part {} of example {} yet, fetching...", part, index);
+        if let Err(err) = fetch_examples(year, day) {
+            panic!("failed to fetch examples for {} day {}: {}", year, day, format_error(err.as_ref()));
+        }
+    }
+    ExampleAnswers::load(year, day)
+        .get(index, part)
+        .unwrap_or_else(|| panic!(
+            "no expected answer for part {} of example {} in {}; add it by hand",
+            part, index, example_answers_file_name(year, day).display()))
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"
+        <main>
+        <article class="day-desc"><h2>--- Day 3: Lobby ---</h2>
+        <p>For example:</p>
+        <pre><code>987654321111111
+811111111111119
+</code></pre>
+        <p>In <code>987654321111111</code>, the largest joltage is <code><em>98</em></code>.</p>
+        <p>The total output joltage is <code><em>357</em></code>.</p>
+        </article>
+        <p>Your puzzle answer was <code>17321</code>.</p>
+        <article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+        <p>Now the total output joltage is <code><em>3121910778619</em></code>.</p>
+        <p>Another example:</p>
+        <pre><code>12345</code></pre>
+        <p>Here, the answer is <em>not</em> <code>12</code> but <code><em>42</em></code>.</p>
+        </article>
+        </main>"#;
+
+    #[test]
+    fn examples_and_answers() {
+        let root = parse_html().one(PAGE);
+        let (examples, answers) = extract(&root);
+        assert_eq!(examples, [
+            Example { part: 1, contents: "987654321111111\n811111111111119\n".to_owned() },
+            Example { part: 2, contents: "12345".to_owned() },
+        ]);
+        assert_eq!(answers, [
+            (0, 1, "357".to_owned()),
+            (1, 2, "42".to_owned()),
+        ]);
+    }
+
+    #[test]
+    fn part_2_reuses_example() {
+        let page = PAGE.replace("<p>Another example:</p>", "").replace("<pre><code>12345</code></pre>", "")
+            .replace("<p>Here, the answer is <em>not</em> <code>12</code> but <code><em>42</em></code>.</p>", "");
+        let root = parse_html().one(page);
+        let (examples, answers) = extract(&root);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(answers, [
+            (0, 1, "357".to_owned()),
+            (0, 2, "3121910778619".to_owned()),
+        ]);
+    }
+
+    #[test]
+    fn skips_intermediate_states() {
+        let page = PAGE.replace("<p>Another example:</p>", "<p>After one step:</p>");
+        let root = parse_html().one(page);
+        let (examples, answers) = extract(&root);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(answers, [
+            (0, 1, "357".to_owned()),
+            (0, 2, "42".to_owned()),
+        ]);
+    }
+
+    #[test]
+    fn sidecar_roundtrip() {
+        let mut answers = ExampleAnswers::default();
+        answers.answers.insert((0, 1), "357".to_owned());
+        answers.answers.insert((0, 2), "3121910778619".to_owned());
+        let parsed = ExampleAnswers::parse(&answers.to_string());
+        assert_eq!(parsed, answers);
+        assert_eq!(parsed.get(0, 2), Some("3121910778619"));
+        assert_eq!(parsed.get(1, 1), None);
+    }
+
+    #[test]
+    fn parts_roundtrip() {
+        let mut parts = ExampleParts::default();
+        parts.parts.insert(0, 1);
+        parts.parts.insert(1, 2);
+        let parsed = ExampleParts::parse(&parts.to_string());
+        assert_eq!(parsed, parts);
+        assert_eq!(parsed.get(1), Some(2));
+        assert_eq!(parsed.get(2), None);
+    }
+}
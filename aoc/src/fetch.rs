@@ -0,0 +1,111 @@
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::calendar::{format_countdown, is_unlocked, num_days, unlock_time};
+use crate::examples::fetch_examples;
+use crate::tool::usage_error;
+use crate::{ensure_dir_exists, example_file_name, fetch_input, format_error, input_file_name, puzzle_file_name};
+
+const USAGE: &str = "\
+Usage: aoc fetch [OPTIONS]
+
+Downloads the input, examples and puzzle page for every day that is unlocked, skipping anything
+that is already cached.
+
+Options:
+  --wait             Then wait for the next day to unlock, and fetch it as soon as it does
+  -h, --help         Print this help and exit";
+
+/// How often to try fetching a day that just unlocked; the site may lag behind our clock a bit.
+const UNLOCK_ATTEMPTS: u32 = 5;
+const UNLOCK_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Runs the `fetch` command with the given arguments, not including the command name.
+pub fn run_fetch(year: u32, args: impl IntoIterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut wait = false;
+    for arg in args {
+        match arg.as_str() {
+            "--wait" => wait = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => usage_error(USAGE, &format!("unexpected argument {}", arg)),
+        }
+    }
+
+    let now = SystemTime::now();
+    let mut failures = 0;
+    for day in (1..=num_days(year)).filter(|&day| is_unlocked(year, day, now)) {
+        if let Err(err) = fetch_day(year, day) {
+            eprintln!("Failed to fetch {} day {}: {}", year, day, format_error(err.as_ref()));
+            failures += 1;
+        }
+    }
+
+    if wait {
+        match (1..=num_days(year)).find(|&day| !is_unlocked(year, day, SystemTime::now())) {
+            Some(day) => {
+                wait_for_unlock(year, day);
+                fetch_unlocked_day(year, day)?;
+            }
+            None => println!("All days of {} are unlocked", year),
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!("failed to fetch {} days", failures).into());
+    }
+    Ok(())
+}
+
+/// Fetches whatever is not cached yet for the given day, and prints what it did.
+pub(crate) fn fetch_day(year: u32, day: u32) -> Result<(), Box<dyn Error>> {
+    let input_file_name = input_file_name(year, day);
+    let input = if input_file_name.exists() {
+        "cached"
+    } else {
+        let contents = fetch_input(year, day)?;
+        ensure_dir_exists(&input_file_name)?;
+        fs::write(&input_file_name, contents)?;
+        "fetched"
+    };
+    let puzzle = if puzzle_file_name(year, day).exists() && example_file_name(year, day, 0).exists() {
+        "cached".to_owned()
+    } else {
+        format!("fetched, {} examples", fetch_examples(year, day)?)
+    };
+    println!("Day {:2}: input {}, puzzle {}", day, input, puzzle);
+    Ok(())
+}
+
+/// Fetches a day that has just unlocked, retrying a few times in case the site is not quite there
+/// yet.
+fn fetch_unlocked_day(year: u32, day: u32) -> Result<(), Box<dyn Error>> {
+    let mut attempt = 1;
+    loop {
+        match fetch_day(year, day) {
+            Ok(()) => return Ok(()),
+            Err(err) if attempt < UNLOCK_ATTEMPTS => {
+                eprintln!("Failed to fetch {} day {}, retrying: {}", year, day, format_error(err.as_ref()));
+                thread::sleep(UNLOCK_RETRY_DELAY);
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Sleeps until the given day unlocks, showing a countdown.
+fn wait_for_unlock(year: u32, day: u32) {
+    let unlock = unlock_time(year, day);
+    while let Ok(remaining) = unlock.duration_since(SystemTime::now()) {
+        eprint!("\rDay {} unlocks in {} ", day, format_countdown(remaining));
+        let _ = io::stderr().flush();
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+    eprintln!("\rDay {} is unlocked!{}", day, " ".repeat(20));
+}
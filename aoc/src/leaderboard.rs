@@ -0,0 +1,217 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+
+use crate::calendar::{format_countdown, unlock_time};
+use crate::client::send_get_request;
+use crate::tool::usage_error;
+use crate::{ensure_dir_exists, leaderboard_file_name};
+
+const USAGE: &str = "\
+Usage: aoc leaderboard [OPTIONS] ID
+
+Prints the standings of the private leaderboard with the given ID, the time each member took to get
+each star after the puzzle unlocked, and the time from part 1 to part 2. The ID is the number at the
+end of the leaderboard's URL. The leaderboard is fetched at most once every 15 minutes, as the site
+asks; in between, the cached copy is used.
+
+Options:
+  -h, --help         Print this help and exit";
+
+/// How long to use a cached copy of a leaderboard before fetching it again. The site asks not to
+/// fetch the leaderboard API more often than this.
+const POLL_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Runs the `leaderboard` command with the given arguments, not including the command name.
+pub fn run_leaderboard(year: u32, args: impl IntoIterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut id = None;
+    for arg in args {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => match arg.parse::<u64>() {
+                Ok(value) if id.is_none() => id = Some(value),
+                _ => usage_error(USAGE, &format!("unexpected argument {}", arg)),
+            },
+        }
+    }
+    let Some(id) = id else {
+        usage_error(USAGE, "missing leaderboard ID");
+    };
+
+    let (json, fetched) = load_leaderboard(year, id)?;
+    let leaderboard = Leaderboard::parse(&json)?;
+    let age = fetched.elapsed().unwrap_or_default();
+    if age < Duration::from_secs(60) {
+        println!("Private leaderboard {} for {}, fetched just now", id, year);
+    } else {
+        println!("Private leaderboard {} for {}, as fetched {} ago", id, year, format_countdown(age));
+    }
+    println!();
+    print!("{}", leaderboard.standings());
+    println!();
+    print!("{}", leaderboard.star_times(year));
+    Ok(())
+}
+
+/// Returns the leaderboard's JSON and when it was fetched, using the cached copy if it is recent
+/// enough.
+fn load_leaderboard(year: u32, id: u64) -> Result<(String, SystemTime), Box<dyn Error>> {
+    let file_name = leaderboard_file_name(year, id);
+    if let Ok(modified) = fs::metadata(&file_name).and_then(|metadata| metadata.modified()) {
+        if modified.elapsed().map_or(true, |age| age < POLL_INTERVAL) {
+            return Ok((fs::read_to_string(&file_name)?, modified));
+        }
+    }
+    let json = send_get_request(&format!("/{}/leaderboard/private/view/{}.json", year, id))?;
+    if json.trim_start().starts_with('<') {
+        return Err("the site returned an HTML page instead of the leaderboard; check that the ID is right and \
+            that you are a member".into());
+    }
+    ensure_dir_exists(&file_name)?;
+    fs::write(&file_name, &json)?;
+    Ok((json, SystemTime::now()))
+}
+
+/// A private leaderboard, as returned by the site's JSON API.
+#[derive(Debug)]
+struct Leaderboard {
+    /// Members in order of their standing.
+    members: Vec<Member>,
+}
+
+#[derive(Debug)]
+struct Member {
+    name: String,
+    local_score: u64,
+    stars: u64,
+    last_star_ts: u64,
+    /// For each day with at least one star, the Unix timestamps at which parts 1 and 2 were solved.
+    star_times: BTreeMap<u32, [Option<u64>; 2]>,
+}
+
+impl Leaderboard {
+    fn parse(json: &str) -> Result<Self, Box<dyn Error>> {
+        let root: Value = serde_json::from_str(json)?;
+        let members = root["members"].as_object().ok_or("leaderboard has no members")?;
+        let mut members = members.values().map(Member::parse).collect::<Result<Vec<_>, _>>()?;
+        members.sort_by(|a, b| {
+            b.local_score.cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.name.cmp(&b.name))
+        });
+        Ok(Leaderboard { members })
+    }
+
+    /// Formats the members in order of their local score.
+    fn standings(&self) -> String {
+        let mut out = format!("{:>4}  {:>5}  {:>5}  Name\n", "Rank", "Score", "Stars");
+        for (index, member) in self.members.iter().enumerate() {
+            out += &format!("{:>4}  {:>5}  {:>5}  {}\n", index + 1, member.local_score, member.stars, member.name);
+        }
+        out
+    }
+
+    /// Formats, for each day, the time each member took to get each star since the puzzle
+    /// unlocked, and the time from the first star to the second.
+    fn star_times(&self, year: u32) -> String {
+        let days = self.members.iter()
+            .flat_map(|member| member.star_times.keys().copied())
+            .collect::<BTreeSet<_>>();
+        let name_width = self.members.iter().map(|member| member.name.chars().count()).max().unwrap_or(0);
+        let mut out = String::new();
+        for day in days {
+            let unlock = unlock_time(year, day).duration_since(UNIX_EPOCH).unwrap().as_secs();
+            out += &format!("Day {}:\n", day);
+            out += &format!("  {:name_width$}  {:>11}  {:>11}  {:>11}\n", "", "Part 1", "Part 2", "Delta");
+            let mut times = self.members.iter()
+                .filter_map(|member| Some((member, member.star_times.get(&day)?)))
+                .collect::<Vec<_>>();
+            times.sort_by_key(|(_, [part_1, part_2])| (part_2.is_none(), *part_2, *part_1));
+            for (member, [part_1, part_2]) in times {
+                let since_unlock = |ts: &Option<u64>| ts.map_or(String::new(), |ts| format_seconds(ts.saturating_sub(unlock)));
+                let delta = match (part_1, part_2) {
+                    (Some(part_1), Some(part_2)) => format_seconds(part_2.saturating_sub(*part_1)),
+                    _ => String::new(),
+                };
+                let line = format!(
+                    "  {:name_width$}  {:>11}  {:>11}  {:>11}",
+                    member.name, since_unlock(part_1), since_unlock(part_2), delta);
+                out += line.trim_end();
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+impl Member {
+    fn parse(value: &Value) -> Result<Self, Box<dyn Error>> {
+        let number = |key: &str| value[key].as_u64().ok_or_else(|| format!("member has no valid {}", key));
+        let id = number("id")?;
+        let mut star_times = BTreeMap::new();
+        if let Some(days) = value["completion_day_level"].as_object() {
+            for (day, parts) in days {
+                let day = day.parse::<u32>().map_err(|_| format!("invalid day {:?}", day))?;
+                let star_time = |part: &str| parts[part]["get_star_ts"].as_u64();
+                star_times.insert(day, [star_time("1"), star_time("2")]);
+            }
+        }
+        Ok(Member {
+            name: value["name"].as_str().map_or_else(|| format!("(anonymous user #{})", id), str::to_owned),
+            local_score: number("local_score")?,
+            stars: number("stars")?,
+            last_star_ts: number("last_star_ts")?,
+            star_times,
+        })
+    }
+}
+
+fn format_seconds(secs: u64) -> String {
+    format_countdown(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../testdata/leaderboard.json");
+
+    #[test]
+    fn standings() {
+        let leaderboard = Leaderboard::parse(SAMPLE).unwrap();
+        assert_eq!(
+            leaderboard.standings(),
+            "Rank  Score  Stars  Name\n\
+            \x20  1     11      4  Alice\n\
+            \x20  2      8      3  Bob\n\
+            \x20  3      0      0  (anonymous user #1003)\n");
+    }
+
+    #[test]
+    fn star_times() {
+        let leaderboard = Leaderboard::parse(SAMPLE).unwrap();
+        assert_eq!(
+            leaderboard.star_times(2025),
+            "Day 1:\n\
+            \x20                              Part 1       Part 2        Delta\n\
+            \x20 Alice                      00:05:12     00:07:30     00:02:18\n\
+            \x20 Bob                        00:15:00     02:15:00     02:00:00\n\
+            Day 2:\n\
+            \x20                              Part 1       Part 2        Delta\n\
+            \x20 Alice                      00:06:40     00:15:00     00:08:20\n\
+            \x20 Bob                        00:03:20\n");
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Leaderboard::parse("{}").is_err());
+        assert!(Leaderboard::parse(r#"{"members": {"1": {"id": 1}}}"#).is_err());
+    }
+}
@@ -1,19 +1,68 @@
+use std::any::Any;
 use std::error::Error;
+use std::fmt;
 use std::fs;
-use std::io::BufRead;
 use std::io;
-use std::path::Path;
-use std::time::Instant;
-
-use kuchiki::parse_html;
-use kuchiki::traits::TendrilSink;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
 
 pub use aoc_proc_macros::*;
 
-/// Generates a `fn main()` implementation. Takes one argument, the run function, which should
-/// accept a string reference to the input and return the puzzle's output. Example:
+pub use all::run_all;
+pub use answers::{Check, KnownAnswers};
+pub use args::{Options, Source};
+pub use bench::{bench, FormatDuration, Stats};
+pub use calendar::{num_days, unlock_time};
+pub use config::Config;
+pub use examples::{example_answer, ExampleAnswers, ExampleParts};
+pub use memory::{FormatBytes, MemoryStats};
+pub use ocr::{draw, recognize};
+pub use paths::{set_manifest_dir, set_package_name};
+pub use puzzle::{puzzle_page, render_puzzle, Format};
+pub use record::{checksum, OutputFormat, Record};
+pub use runner::{Parsed, Parser, Parts, Run, Solution, Whole};
+pub use session::user_config_dir;
+pub use submit::{submit, Verdict};
+pub use tool::run_tool;
+
+mod all;
+mod answers;
+mod args;
+mod bench;
+mod calendar;
+#[doc(hidden)]
+pub mod chain;
+mod client;
+mod config;
+mod examples;
+mod fetch;
+mod leaderboard;
+mod memory;
+mod names;
+mod ocr;
+mod paths;
+mod puzzle;
+mod record;
+#[doc(hidden)]
+pub mod registry;
+mod runner;
+mod scaffold;
+mod session;
+mod status;
+mod submit;
+mod table;
+mod tool;
+
+use client::send_get_request;
+use examples::fetch_examples;
+use paths::data_file;
+use runner::TryParse;
+
+/// Generates a `fn main()` implementation. In its simplest form, it takes one argument, the run
+/// function, which should accept a string reference to the input and return the puzzle's output.
+/// Example:
 ///
-/// ```
+/// ```ignore
 /// fn run(input: &str) -> (u64, u64) {
 ///     // ...
 /// }
@@ -21,172 +70,570 @@ pub use aoc_proc_macros::*;
 /// aoc::main!(run);
 /// ```
 ///
-/// This needs to be a macro so that it can determine the year from the `CARGO_PKG_NAME` and
-/// `CARGO_BIN_NAME` environment variables, set by Cargo during compilation of the main crate.
+/// Alternatively, pass separate functions for each part, optionally preceded by a parse function
+/// whose output is passed to both parts by reference. Each stage is then timed separately. If the
+/// parse function returns a `Result`, the parts get the parsed value, and an error is reported as
+/// an error in the parse stage:
+///
+/// ```ignore
+/// aoc::main!(part_1, part_2);
+/// aoc::main!(parse => part_1, part_2);
+/// ```
+///
+/// This needs to be a macro so that it can determine the year and day from the `CARGO_PKG_NAME` and
+/// `CARGO_BIN_NAME` environment variables, set by Cargo during compilation of the main crate (see
+/// [`year!`] and [`day!`]). If the names do not say, pass them after the functions, e.g.
+/// `aoc::main!(run, day = 3)` or `aoc::main!(run, year = 2024, day = 3)`. Other macros such as
+/// `example!` and `example_tests!` do not see these, so pass them the same, e.g.
+/// `aoc::example!(0, day = 3)`. It also
+/// captures `CARGO_MANIFEST_DIR`, so that inputs, examples and answers are stored next to the
+/// crate's `Cargo.toml` regardless of the working directory (see [`Config`] to store them
+/// elsewhere).
+///
+/// The generated `main` runs the solution on the puzzle input and checks the answers against the
+/// known ones. It takes options to run on examples, submit, benchmark and more; pass `--help` for
+/// the list. Because the benchmark calls them repeatedly, the functions must be `Fn`.
+///
+/// Instead of this macro, the functions can be registered with the [`part`] and [`parse`]
+/// attributes, which generate the same `main`.
 #[macro_export]
 macro_rules! main {
-    ($run_fn:expr) => {
+    ($parse_fn:expr => $part_1_fn:expr, $part_2_fn:expr $(, $key:ident = $value:literal)* $(,)?) => {
         fn main() {
-            $crate::main($crate::year!(), $crate::day!(), $run_fn);
+            $crate::set_manifest_dir(env!("CARGO_MANIFEST_DIR"));
+            $crate::set_package_name(env!("CARGO_PKG_NAME"));
+            $crate::main_parsed(
+                $crate::year!($($key = $value),*), $crate::day!($($key = $value),*),
+                |input: &str| $crate::__chain_errors!(($parse_fn)(input)),
+                |parsed: &_| $crate::__chain_errors!(($part_1_fn)(parsed)),
+                |parsed: &_| $crate::__chain_errors!(($part_2_fn)(parsed)));
         }
-    }
+    };
+    ($run_fn:expr $(, $key:ident = $value:literal)* $(,)?) => {
+        fn main() {
+            $crate::set_manifest_dir(env!("CARGO_MANIFEST_DIR"));
+            $crate::set_package_name(env!("CARGO_PKG_NAME"));
+            $crate::main(
+                $crate::year!($($key = $value),*), $crate::day!($($key = $value),*),
+                |input: &str| $crate::__chain_errors!(($run_fn)(input)));
+        }
+    };
+    ($part_1_fn:expr, $part_2_fn:expr $(, $key:ident = $value:literal)* $(,)?) => {
+        fn main() {
+            $crate::set_manifest_dir(env!("CARGO_MANIFEST_DIR"));
+            $crate::set_package_name(env!("CARGO_PKG_NAME"));
+            $crate::main_parts(
+                $crate::year!($($key = $value),*), $crate::day!($($key = $value),*),
+                |input: &str| $crate::__chain_errors!(($part_1_fn)(input)),
+                |input: &str| $crate::__chain_errors!(($part_2_fn)(input)));
+        }
+    };
 }
 
 #[macro_export]
 macro_rules! input {
-    () => {
-        $crate::input($crate::year!(), $crate::day!())
-    }
+    ($($key:ident = $value:literal),* $(,)?) => {{
+        $crate::set_manifest_dir(env!("CARGO_MANIFEST_DIR"));
+        $crate::set_package_name(env!("CARGO_PKG_NAME"));
+        $crate::input($crate::year!($($key = $value),*), $crate::day!($($key = $value),*))
+    }}
+}
+
+#[macro_export]
+macro_rules! try_input {
+    ($($key:ident = $value:literal),* $(,)?) => {{
+        $crate::set_manifest_dir(env!("CARGO_MANIFEST_DIR"));
+        $crate::set_package_name(env!("CARGO_PKG_NAME"));
+        $crate::try_input($crate::year!($($key = $value),*), $crate::day!($($key = $value),*))
+    }}
 }
 
 #[macro_export]
 macro_rules! example {
-    ($idx:expr) => {
-        $crate::example($crate::year!(), $crate::day!(), $idx)
+    ($idx:expr $(, $key:ident = $value:literal)* $(,)?) => {{
+        $crate::set_manifest_dir(env!("CARGO_MANIFEST_DIR"));
+        $crate::set_package_name(env!("CARGO_PKG_NAME"));
+        $crate::example($crate::year!($($key = $value),*), $crate::day!($($key = $value),*), $idx)
+    }}
+}
+
+#[macro_export]
+macro_rules! try_example {
+    ($idx:expr $(, $key:ident = $value:literal)* $(,)?) => {{
+        $crate::set_manifest_dir(env!("CARGO_MANIFEST_DIR"));
+        $crate::set_package_name(env!("CARGO_PKG_NAME"));
+        $crate::try_example($crate::year!($($key = $value),*), $crate::day!($($key = $value),*), $idx)
+    }}
+}
+
+/// Returns the expected answer to part `$part` of example `$idx`, as scraped from the puzzle
+/// description into `examples/NN.answers`.
+#[macro_export]
+macro_rules! example_answer {
+    ($idx:expr, $part:expr $(, $key:ident = $value:literal)* $(,)?) => {{
+        $crate::set_manifest_dir(env!("CARGO_MANIFEST_DIR"));
+        $crate::set_package_name(env!("CARGO_PKG_NAME"));
+        $crate::example_answer($crate::year!($($key = $value),*), $crate::day!($($key = $value),*), $idx, $part)
+    }}
+}
+
+/// Asserts that an answer matches the expected answer to part `$part` of example `$idx`. Example:
+///
+/// ```ignore
+/// aoc::assert_example!(part_1(&aoc::example!(0)), 0, 1);
+/// ```
+#[macro_export]
+macro_rules! assert_example {
+    ($answer:expr, $idx:expr, $part:expr $(, $key:ident = $value:literal)* $(,)?) => {
+        assert_eq!(
+            $crate::Answer::show(&$answer),
+            $crate::example_answer!($idx, $part $(, $key = $value)*),
+            "wrong answer to part {} of example {}", $part, $idx)
     }
 }
 
+/// Asserts that `answer` computes the expected answer to the given part of an example; used by the
+/// tests that `aoc::example_tests!` generates. The message names the example file on failure.
+#[doc(hidden)]
+pub fn assert_example_part<F>(year: u32, day: u32, index: usize, part: u32, answer: F, expected: impl Answer)
+where F: FnOnce(&str) -> Option<String>
+{
+    let input = example(year, day, index);
+    let answer = answer(&input);
+    assert_eq!(
+        answer.as_deref().unwrap_or("unsolved"),
+        expected.show(),
+        "wrong answer to part {} of {}", part, example_file_name(year, day, index).display());
+}
+
+/// The return type of a solution. Implemented for all primitive integers, `char`, strings, `Vec`s
+/// (shown comma-separated), `Option`s (`None` meaning not solved yet), `Result`s, and tuples with
+/// one answer per part. Wrap any other `Display` type in [`Displayed`] to use it as an answer.
+///
+/// If an answer spans several lines and draws letters in the site's block font, the runner reads
+/// the letters (see [`recognize`]) and checks and submits those instead.
 pub trait Answer {
     fn show(&self) -> String;
+
+    /// Returns the answer as the answer to a single part, or `None` if it is not solved yet.
+    fn part(&self) -> Option<String> {
+        Some(self.show())
+    }
+
+    /// Returns the answers to the individual parts of the puzzle, in order. Most types represent
+    /// the answer to a single part.
+    fn parts(&self) -> Vec<Option<String>> {
+        vec![self.part()]
+    }
+
+    /// Returns an error message if the solution failed to produce an answer.
+    fn error(&self) -> Option<String> {
+        None
+    }
 }
 
-impl Answer for usize {
+macro_rules! impl_answer_with_to_string {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn show(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    }
+}
+
+impl_answer_with_to_string!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char);
+
+/// Shows text as is, but starting on a new line if it spans several lines, so that drawings line
+/// up.
+fn show_text(text: &str) -> String {
+    if text.contains('\n') {
+        "\n".to_owned() + text
+    } else {
+        text.to_owned()
+    }
+}
+
+impl Answer for str {
     fn show(&self) -> String {
-        self.to_string()
+        show_text(self)
     }
 }
 
-impl Answer for u64 {
+impl Answer for String {
     fn show(&self) -> String {
-        self.to_string()
+        show_text(self)
     }
 }
 
-impl Answer for i64 {
+impl<T> Answer for &T where T: Answer + ?Sized {
     fn show(&self) -> String {
-        self.to_string()
+        (**self).show()
+    }
+
+    fn part(&self) -> Option<String> {
+        (**self).part()
+    }
+
+    fn parts(&self) -> Vec<Option<String>> {
+        (**self).parts()
+    }
+
+    fn error(&self) -> Option<String> {
+        (**self).error()
     }
 }
 
-impl Answer for String {
+/// An answer that may not be computed yet, e.g. while part 2 is still being worked on. `None` is
+/// shown as "unsolved", and is not checked, accepted or submitted.
+impl<T> Answer for Option<T> where T: Answer {
     fn show(&self) -> String {
-        if self.contains('\n') {
-            "\n".to_owned() + self
-        } else {
-            self.to_owned()
+        match self {
+            Some(answer) => answer.show(),
+            None => "unsolved".to_owned(),
         }
     }
+
+    fn part(&self) -> Option<String> {
+        self.as_ref().and_then(Answer::part)
+    }
+
+    fn error(&self) -> Option<String> {
+        self.as_ref().and_then(Answer::error)
+    }
+}
+
+/// A list of numbers or other values, shown comma-separated as the site expects.
+impl<T> Answer for Vec<T> where T: Answer {
+    fn show(&self) -> String {
+        self.iter().map(Answer::show).collect::<Vec<_>>().join(",")
+    }
+}
+
+/// Any `Display` type used as an answer, such as a grid that draws the answer. Example:
+///
+/// ```ignore
+/// fn part_2(input: &str) -> aoc::Displayed<Grid<Pixel>> {
+///     aoc::Displayed(grid)
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Displayed<T>(pub T);
+
+impl<T> Answer for Displayed<T> where T: fmt::Display {
+    fn show(&self) -> String {
+        show_text(&self.0.to_string())
+    }
+}
+
+impl<T> Answer for (T,) where T: Answer {
+    fn show(&self) -> String {
+        format!("Part 1: {}", self.0.show())
+    }
+
+    fn parts(&self) -> Vec<Option<String>> {
+        vec![self.0.part()]
+    }
+
+    fn error(&self) -> Option<String> {
+        self.0.error()
+    }
 }
 
 impl<T, U> Answer for (T, U) where T: Answer, U: Answer {
     fn show(&self) -> String {
         format!("Part 1: {}\nPart 2: {}", self.0.show(), self.1.show())
     }
+
+    fn parts(&self) -> Vec<Option<String>> {
+        vec![self.0.part(), self.1.part()]
+    }
+
+    fn error(&self) -> Option<String> {
+        self.0.error().or_else(|| self.1.error())
+    }
 }
 
-pub fn main<A: Answer, F: FnOnce(&str) -> A>(year: u32, day: u32, run_fn: F) {
-    let input = input(year, day);
+/// Three answers, e.g. to report an intermediate result alongside the two parts.
+impl<T, U, V> Answer for (T, U, V) where T: Answer, U: Answer, V: Answer {
+    fn show(&self) -> String {
+        format!("Part 1: {}\nPart 2: {}\nPart 3: {}", self.0.show(), self.1.show(), self.2.show())
+    }
+
+    fn parts(&self) -> Vec<Option<String>> {
+        vec![self.0.part(), self.1.part(), self.2.part()]
+    }
 
-    let start = Instant::now();
-    let answer = run_fn(&input);
-    let duration = start.elapsed();
+    fn error(&self) -> Option<String> {
+        self.0.error().or_else(|| self.1.error()).or_else(|| self.2.error())
+    }
+}
+
+/// A fallible answer. The error is reported by the runner, including its cause chain if it is a
+/// boxed `std::error::Error`, or any other `Error` returned directly by a function passed to the
+/// macros; other types are formatted with `{:#}`, which includes the cause chain for e.g.
+/// `anyhow::Error`.
+impl<T, E> Answer for Result<T, E> where T: Answer, E: fmt::Display + 'static {
+    fn show(&self) -> String {
+        match self {
+            Ok(answer) => answer.show(),
+            Err(err) => format!("error: {}", err),
+        }
+    }
+
+    fn part(&self) -> Option<String> {
+        self.as_ref().ok().and_then(Answer::part)
+    }
+
+    fn parts(&self) -> Vec<Option<String>> {
+        match self {
+            Ok(answer) => answer.parts(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn error(&self) -> Option<String> {
+        match self {
+            Ok(answer) => answer.error(),
+            Err(err) => Some(error_message(err)),
+        }
+    }
+}
+
+/// Formats the error returned by a solution or parse function: with its causes if it is a boxed
+/// `std::error::Error`, and with `{:#}` otherwise.
+fn error_message<E: fmt::Display + 'static>(err: &E) -> String {
+    let any: &dyn Any = err;
+    if let Some(err) = any.downcast_ref::<Box<dyn Error>>() {
+        format_error(err.as_ref())
+    } else if let Some(err) = any.downcast_ref::<Box<dyn Error + Send + Sync>>() {
+        format_error(err.as_ref())
+    } else {
+        format!("{:#}", err)
+    }
+}
+
+/// The return type of a parse function: the parsed input `P` itself, or a `Result` of it. An error
+/// is reported like an error from a part, with `parse` as the failing stage.
+pub trait ParseOutput<P> {
+    fn into_parsed(self) -> Result<P, String>;
+}
+
+impl<P> ParseOutput<P> for P {
+    fn into_parsed(self) -> Result<P, String> {
+        Ok(self)
+    }
+}
+
+impl<P, E> ParseOutput<P> for Result<P, E> where E: fmt::Display + 'static {
+    fn into_parsed(self) -> Result<P, String> {
+        self.map_err(|err| error_message(&err))
+    }
+}
+
+/// Formats an error followed by its chain of causes, one per line.
+pub fn format_error(err: &(dyn Error + 'static)) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message += &format!("\n  caused by: {}", cause);
+        source = cause.source();
+    }
+    message
+}
+
+/// Runs a solution consisting of a single function that returns the answers to all parts.
+pub fn main<A: Answer, F: Fn(&str) -> A>(year: u32, day: u32, run_fn: F) {
+    runner::run(year, day, Whole(run_fn));
+}
+
+/// Runs a solution consisting of separate functions for each part.
+pub fn main_parts<A, B, F1, F2>(year: u32, day: u32, part_1: F1, part_2: F2)
+where A: Answer, B: Answer, F1: Fn(&str) -> A, F2: Fn(&str) -> B
+{
+    runner::run(year, day, Parts(part_1, part_2));
+}
+
+/// Runs a solution consisting of a parse function and separate functions for each part.
+pub fn main_parsed<P, R, A, B, FP, F1, F2>(year: u32, day: u32, parse: FP, part_1: F1, part_2: F2)
+where R: ParseOutput<P>, A: Answer, B: Answer, FP: Fn(&str) -> R, F1: Fn(&P) -> A, F2: Fn(&P) -> B
+{
+    runner::run(year, day, Parsed(TryParse(parse, PhantomData), part_1, part_2));
+}
 
-    println!(
-        "Answer to {} day {} ({}.{:03} s):\n{}",
-        year, day, duration.as_secs(), duration.subsec_millis(), answer.show());
+/// Runs a solution registered with the `#[aoc::parse]` and `#[aoc::part]` attributes; called from
+/// the `main` that `#[aoc::part(1)]` generates.
+#[doc(hidden)]
+pub fn main_registered<P, R, A, B, FP, F1, F2>(year: u32, day: u32, parse: FP, part_1: F1, part_2: F2)
+where R: ParseOutput<P>, A: Answer, B: Answer, FP: registry::ParseFn<R>, F1: Fn(&P) -> A, F2: Fn(&P) -> B
+{
+    runner::run(year, day, Parsed(TryParse(move |input: &str| parse.parse(input), PhantomData), part_1, part_2));
 }
 
+/// Returns the puzzle input, fetching it if it is not cached yet. Panics on failure.
 pub fn input(year: u32, day: u32) -> String {
+    try_input(year, day).unwrap_or_else(|err| panic!("{}", format_error(err.as_ref())))
+}
+
+/// Returns the puzzle input, fetching it if it is not cached yet.
+pub fn try_input(year: u32, day: u32) -> Result<String, Box<dyn Error>> {
     let input_file_name = input_file_name(year, day);
     fs::read_to_string(&input_file_name)
         .or_else(|_err| -> Result<String, Box<dyn Error>> {
-            println!("Input file {} could not be read, fetching...", input_file_name);
-            let contents = fetch_input(year, day)?;
+            eprintln!("Input file {} could not be read, fetching...", input_file_name.display());
+            let contents = fetch_input(year, day)
+                .map_err(|err| format!("failed to fetch input for {} day {}: {}", year, day, err))?;
             ensure_dir_exists(&input_file_name)?;
             fs::write(&input_file_name, &contents)?;
             Ok(contents)
         })
-        .unwrap()
 }
 
+/// Returns an example from the puzzle description, fetching the examples if they are not cached
+/// yet. Panics on failure.
 pub fn example(year: u32, day: u32, index: usize) -> String {
+    try_example(year, day, index).unwrap_or_else(|err| panic!("{}", format_error(err.as_ref())))
+}
+
+/// Returns an example from the puzzle description, fetching the examples if they are not cached
+/// yet.
+pub fn try_example(year: u32, day: u32, index: usize) -> Result<String, Box<dyn Error>> {
     let example_file_name = example_file_name(year, day, index);
     fs::read_to_string(&example_file_name)
         .or_else(|_err| -> Result<String, Box<dyn Error>> {
-            println!("Example file {} could not be read, fetching...", example_file_name);
-            let num_examples = fetch_examples(year, day)?;
+            eprintln!("Example file {} could not be read, fetching...", example_file_name.display());
+            let num_examples = fetch_examples(year, day)
+                .map_err(|err| format!("failed to fetch examples for {} day {}: {}", year, day, err))?;
             if index >= num_examples {
-                panic!(
+                return Err(format!(
                     "tried to read example {} but there are only {} examples for year {}, day {}",
-                    index, num_examples, year, day);
+                    index, num_examples, year, day).into());
             }
-            let contents = fs::read_to_string(&example_file_name)
-                .unwrap();
+            let contents = fs::read_to_string(&example_file_name)?;
             Ok(contents)
         })
-        .unwrap()
 }
 
-fn input_file_name(_year: u32, day: u32) -> String {
-    format!("inputs/{:02}.in", day)
+fn input_file_name(year: u32, day: u32) -> PathBuf {
+    data_file(year, "inputs", format!("{:02}.in", day))
 }
 
-fn example_file_name(_year: u32, day: u32, index: usize) -> String {
-    format!("examples/{:02}-{}.example", day, index)
+fn example_file_name(year: u32, day: u32, index: usize) -> PathBuf {
+    data_file(year, "examples", format!("{:02}-{}.example", day, index))
 }
 
-fn load_session_cookie() -> Result<String, io::Error> {
-    let cookie_file_name = ".session_cookie";
-    fs::read_to_string(cookie_file_name)
-        .map(|s| s.trim().to_string())
-        .or_else(|_err| -> Result<String, io::Error> {
-            println!("No session cookie found. Please log in to https://adventofcode.com/ in your browser, open the browser console, copy the value of the 'session' cookie, and paste it here:");
-            let mut line = String::new();
-            io::stdin().lock().read_line(&mut line)?;
-            ensure_dir_exists(&cookie_file_name)?;
-            fs::write(&cookie_file_name, &line)?;
-            Ok(line.trim().to_string())
-        })
+fn example_answers_file_name(year: u32, day: u32) -> PathBuf {
+    data_file(year, "examples", format!("{:02}.answers", day))
+}
+
+fn example_parts_file_name(year: u32, day: u32) -> PathBuf {
+    data_file(year, "examples", format!("{:02}.parts", day))
+}
+
+fn puzzle_file_name(year: u32, day: u32) -> PathBuf {
+    data_file(year, "puzzles", format!("{:02}.html", day))
+}
+
+fn leaderboard_file_name(year: u32, id: u64) -> PathBuf {
+    data_file(year, "leaderboards", format!("{}.json", id))
+}
+
+fn answers_file_name(year: u32, day: u32) -> PathBuf {
+    data_file(year, "answers", format!("{:02}.txt", day))
+}
+
+fn submissions_file_name(year: u32, day: u32) -> PathBuf {
+    data_file(year, "submissions", format!("{:02}.txt", day))
 }
 
 fn fetch_input(year: u32, day: u32) -> Result<String, Box<dyn Error>> {
-    let url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
-    send_get_request(&url)?
-        .text()
-        .map_err(From::from)
-}
-
-fn fetch_examples(year: u32, day: u32) -> Result<usize, Box<dyn Error>> {
-    let url = format!("https://adventofcode.com/{}/day/{}", year, day);
-    let mut response = send_get_request(&url)?;
-    let root = parse_html().from_utf8().read_from(&mut response)?;
-    let mut num_examples = 0;
-    for (index, node) in root.select("pre").unwrap().enumerate() {
-        let file_name = example_file_name(year, day, index);
-        ensure_dir_exists(&file_name)?;
-        fs::write(&file_name, node.text_contents())?;
-        num_examples += 1;
-    }
-    Ok(num_examples)
-}
-
-fn send_get_request(url: &str) -> Result<reqwest::blocking::Response, Box<dyn Error>> {
-    let client = reqwest::blocking::ClientBuilder::new()
-        .user_agent("aoc2022 by ttencate@gmail.com, https://github.com/ttencate/aoc2022.git")
-        .build()
-        .expect("failed to build client");
-    let session_cookie = load_session_cookie()?;
-    let response = client.get(url)
-        .header(reqwest::header::COOKIE, format!("session={}", session_cookie))
-        .send()
-        .expect("request failed")
-        .error_for_status()?;
-    Ok(response)
-}
-
-fn ensure_dir_exists(file_name: &str) -> Result<(), io::Error> {
-    if let Some(parent) = Path::new(file_name).parent() {
+    let input = send_get_request(&format!("/{}/day/{}/input", year, day))?;
+    if input.trim_start().starts_with('<') {
+        return Err("the site returned an HTML page instead of the puzzle input".into());
+    }
+    Ok(input)
+}
+
+fn ensure_dir_exists(file_name: impl AsRef<Path>) -> Result<(), io::Error> {
+    if let Some(parent) = file_name.as_ref().parent() {
         fs::create_dir_all(parent)?;
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Outer(std::num::ParseIntError);
+
+    impl fmt::Display for Outer {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "bad number")
+        }
+    }
+
+    impl Error for Outer {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn result_answer() {
+        let ok: Result<u64, String> = Ok(42);
+        assert_eq!(ok.parts(), [Some("42".to_owned())]);
+        assert_eq!(ok.error(), None);
+
+        let err: Result<u64, String> = Err("oops".to_owned());
+        assert!(err.parts().is_empty());
+        assert_eq!(err.error(), Some("oops".to_owned()));
+
+        let err: Result<(u64, u64), Box<dyn Error>> = Err(Box::new(Outer("x".parse::<u64>().unwrap_err())));
+        assert_eq!(err.error(), Some("bad number\n  caused by: invalid digit found in string".to_owned()));
+    }
+
+    #[test]
+    fn chained_errors() {
+        let err: Result<u64, Outer> = Err(Outer("x".parse::<u64>().unwrap_err()));
+        assert_eq!(err.error(), Some("bad number".to_owned()));
+        let err = crate::__chain_errors!(err);
+        assert_eq!(err.error(), Some("bad number\n  caused by: invalid digit found in string".to_owned()));
+
+        let parsed = crate::__chain_errors!("x".parse::<u64>().map_err(Outer));
+        assert_eq!(ParseOutput::<u64>::into_parsed(parsed), Err("bad number\n  caused by: invalid digit found in string".to_owned()));
+        let err = crate::__chain_errors!(Err::<u64, String>("oops".to_owned()));
+        assert_eq!(err, Err("oops".to_owned()));
+        assert_eq!(crate::__chain_errors!(42u64), 42);
+    }
+
+    #[test]
+    fn primitive_answers() {
+        assert_eq!(7u8.show(), "7");
+        assert_eq!((-7i128).show(), "-7");
+        assert_eq!('x'.show(), "x");
+        assert_eq!("abc".show(), "abc");
+        assert_eq!("#.\n.#".show(), "\n#.\n.#");
+        assert_eq!(vec![1u32, 2, 3].show(), "1,2,3");
+        assert_eq!(Displayed(std::net::Ipv4Addr::LOCALHOST).show(), "127.0.0.1");
+    }
+
+    #[test]
+    fn option_answer() {
+        assert_eq!(Some(42u64).parts(), [Some("42".to_owned())]);
+        assert_eq!(None::<u64>.show(), "unsolved");
+        assert_eq!(None::<u64>.parts(), [None]);
+        assert_eq!((42u64, None::<u64>).parts(), [Some("42".to_owned()), None]);
+    }
+
+    #[test]
+    fn tuple_answers() {
+        assert_eq!((1u8,).parts(), [Some("1".to_owned())]);
+        assert_eq!((1u8, "b", 'c').parts(), [Some("1".to_owned()), Some("b".to_owned()), Some("c".to_owned())]);
+        let with_error: (u64, Result<u64, String>, u64) = (1, Err("oops".to_owned()), 3);
+        assert_eq!(with_error.error(), Some("oops".to_owned()));
+    }
+}
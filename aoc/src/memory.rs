@@ -0,0 +1,153 @@
+use std::fmt;
+
+/// Heap usage of a run, as counted by the allocator that the `alloc-stats` feature installs.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MemoryStats {
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+    /// Total number of bytes requested by those allocations.
+    pub bytes_allocated: u64,
+    /// Highest number of live heap bytes at any point, above what was live at the start.
+    pub peak_bytes: u64,
+}
+
+impl fmt::Display for MemoryStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f, "{} allocations, {} allocated, {} peak",
+            self.allocations, FormatBytes(self.bytes_allocated), FormatBytes(self.peak_bytes))
+    }
+}
+
+/// Formats a number of bytes with a binary prefix, e.g. `1.5 MiB`.
+pub struct FormatBytes(pub u64);
+
+impl fmt::Display for FormatBytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut value = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Runs `f` and returns its heap usage, or `None` if the `alloc-stats` feature is disabled. Counts
+/// allocations on all threads.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    #[cfg(feature = "alloc-stats")]
+    {
+        let (result, stats) = counting::measure(f);
+        (result, Some(stats))
+    }
+    #[cfg(not(feature = "alloc-stats"))]
+    {
+        (f(), None)
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+    use super::MemoryStats;
+
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static BYTES_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+    static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+    static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+    /// Wraps the system allocator, counting what passes through it.
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record(layout.size(), 0);
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record(layout.size(), 0);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            LIVE_BYTES.fetch_sub(layout.size() as u64, Relaxed);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                record(new_size, layout.size());
+            }
+            new_ptr
+        }
+    }
+
+    /// Records an allocation of `size` bytes that replaces one of `freed` bytes.
+    fn record(size: usize, freed: usize) {
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        BYTES_ALLOCATED.fetch_add(size as u64, Relaxed);
+        let live = LIVE_BYTES.fetch_add(size as u64, Relaxed) + size as u64;
+        PEAK_BYTES.fetch_max(live, Relaxed);
+        LIVE_BYTES.fetch_sub(freed as u64, Relaxed);
+    }
+
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, MemoryStats) {
+        let allocations = ALLOCATIONS.load(Relaxed);
+        let bytes_allocated = BYTES_ALLOCATED.load(Relaxed);
+        let live = LIVE_BYTES.load(Relaxed);
+        PEAK_BYTES.store(live, Relaxed);
+        let result = f();
+        let stats = MemoryStats {
+            allocations: ALLOCATIONS.load(Relaxed) - allocations,
+            bytes_allocated: BYTES_ALLOCATED.load(Relaxed) - bytes_allocated,
+            peak_bytes: PEAK_BYTES.load(Relaxed).saturating_sub(live),
+        };
+        (result, stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_bytes() {
+        assert_eq!(FormatBytes(1023).to_string(), "1023 B");
+        assert_eq!(FormatBytes(1536).to_string(), "1.5 KiB");
+        assert_eq!(FormatBytes(5 << 30).to_string(), "5.0 GiB");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn counts() {
+        let (_, stats) = measure(|| {
+            let mut v = Vec::<u8>::with_capacity(1000);
+            v.reserve_exact(2000);
+            drop(v);
+            vec![0u8; 100]
+        });
+        let stats = stats.unwrap();
+        assert!(stats.allocations >= 3);
+        assert!(stats.bytes_allocated >= 3100);
+        assert!(stats.peak_bytes >= 2000);
+    }
+}
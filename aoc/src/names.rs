@@ -0,0 +1,78 @@
+//! Parsing of the names that the year and day are derived from. This file is also compiled into
+//! `aoc_proc_macros` (which cannot depend on this crate), so that `aoc::day!` and the commands that
+//! look for day binaries agree on the naming schemes. It must only use `std`.
+
+/// The first year of Advent of Code.
+pub const FIRST_YEAR: u32 = 2015;
+
+/// Returns the number of puzzles in the given year's event: 25 until 2024, 12 since 2025.
+pub fn num_days(year: u32) -> u32 {
+    if year >= 2025 { 12 } else { 25 }
+}
+
+/// The year and day as encoded in a binary name.
+#[derive(Debug, PartialEq)]
+pub struct BinName {
+    pub year: Option<u32>,
+    pub day: u32,
+}
+
+/// Parses binary names like `01`, `1`, `day1`, `day01_alt` and `2024_03`: the day is the first
+/// number, unless that is a year followed by another number.
+pub fn parse_bin_name(name: &str) -> Option<BinName> {
+    let numbers = numbers(name).collect::<Vec<_>>();
+    let (year, day) = match numbers[..] {
+        [year, day, ..] if parse_year(year).is_some() => (parse_year(year), day),
+        [day, ..] => (None, day),
+        [] => return None,
+    };
+    if day.len() > 2 {
+        return None;
+    }
+    Some(BinName { year, day: day.parse().ok()? })
+}
+
+/// Finds a year in a package name like `aoc2025` or `advent-of-code-2025`.
+pub fn parse_package_name(name: &str) -> Option<u32> {
+    numbers(name).find_map(parse_year)
+}
+
+/// Returns the number if it is a year in which Advent of Code took place (or will).
+pub fn parse_year(number: &str) -> Option<u32> {
+    number.parse().ok().filter(|year| number.len() == 4 && *year >= FIRST_YEAR)
+}
+
+/// Returns the runs of ASCII digits in the name.
+pub fn numbers(name: &str) -> impl Iterator<Item = &str> {
+    name.split(|c: char| !c.is_ascii_digit()).filter(|number| !number.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bin_name(year: Option<u32>, day: u32) -> Option<BinName> {
+        Some(BinName { year, day })
+    }
+
+    #[test]
+    fn bin_names() {
+        assert_eq!(parse_bin_name("01"), bin_name(None, 1));
+        assert_eq!(parse_bin_name("1"), bin_name(None, 1));
+        assert_eq!(parse_bin_name("day12"), bin_name(None, 12));
+        assert_eq!(parse_bin_name("day1_alt"), bin_name(None, 1));
+        assert_eq!(parse_bin_name("day03_part2"), bin_name(None, 3));
+        assert_eq!(parse_bin_name("2024_03"), bin_name(Some(2024), 3));
+        assert_eq!(parse_bin_name("all"), None);
+        assert_eq!(parse_bin_name("123"), None);
+    }
+
+    #[test]
+    fn package_names() {
+        assert_eq!(parse_package_name("aoc2025"), Some(2025));
+        assert_eq!(parse_package_name("advent-of-code-2015"), Some(2015));
+        assert_eq!(parse_package_name("aoc-v2-2024"), Some(2024));
+        assert_eq!(parse_package_name("aoc"), None);
+        assert_eq!(parse_package_name("aoc1999"), None);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::Answer;
+
+/// A block-letter font that the site uses to draw answers.
+struct Font {
+    height: usize,
+    /// Each letter with its rows of pixels, `#` for on and `.` for off.
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+/// The font used in most puzzles: letters 4 pixels wide (Y is 5) and 6 high.
+const SMALL: Font = Font {
+    height: 6,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+/// The font used in e.g. 2018 day 10: letters 6 pixels wide and 10 high.
+const LARGE: Font = Font {
+    height: 10,
+    glyphs: &[
+        ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+        ('B', &["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+        ('C', &[".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+        ('E', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('F', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('G', &[".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+        ('H', &["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+        ('J', &["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+        ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+        ('L', &["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+        ('P', &["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('R', &["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+        ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+        ('Z', &["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+    ],
+};
+
+/// Recognizes the letters in a drawing made with one of the site's fonts. Pixels that are `#` or
+/// `█` are on, anything else is off. Blank rows and columns around and between letters are
+/// ignored. Returns `None` if the drawing is not made up entirely of known letters.
+pub fn recognize(drawing: &str) -> Option<String> {
+    let mut rows: Vec<Vec<bool>> = drawing.lines()
+        .map(|line| line.chars().map(|c| c == '#' || c == '█').collect())
+        .collect();
+    while rows.last().is_some_and(|row| !row.contains(&true)) {
+        rows.pop();
+    }
+    let first_row = rows.iter().position(|row| row.contains(&true))?;
+    rows.drain(..first_row);
+    [SMALL, LARGE].iter()
+        .filter(|font| font.height == rows.len())
+        .find_map(|font| recognize_in_font(&rows, font))
+}
+
+fn recognize_in_font(rows: &[Vec<bool>], font: &Font) -> Option<String> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let pixel = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+    let blank_column = |x: usize| (0..rows.len()).all(|y| !pixel(x, y));
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < width {
+        if blank_column(x) {
+            x += 1;
+            continue;
+        }
+        let (letter, glyph) = font.glyphs.iter()
+            .map(|(letter, glyph)| (*letter, trim_columns(glyph)))
+            .find(|(_, glyph)| {
+                let glyph_width = glyph[0].len();
+                blank_column(x + glyph_width) && glyph.iter().enumerate().all(|(y, row)| {
+                    row.chars().enumerate().all(|(dx, c)| (c == '#') == pixel(x + dx, y))
+                })
+            })?;
+        letters.push(letter);
+        x += glyph[0].len();
+    }
+    Some(letters).filter(|letters| !letters.is_empty())
+}
+
+/// Removes the columns that are blank in all rows from the left and right of a glyph.
+fn trim_columns(glyph: &[&'static str]) -> Vec<&'static str> {
+    let is_blank = |x: usize| glyph.iter().all(|row| row.as_bytes()[x] == b'.');
+    let width = glyph[0].len();
+    let start = (0..width).find(|&x| !is_blank(x)).unwrap_or(0);
+    let end = (0..width).rfind(|&x| !is_blank(x)).map_or(width, |x| x + 1);
+    glyph.iter().map(|row| &row[start..end]).collect()
+}
+
+/// Returns the answer to check and submit for an answer as shown: the letters if it is a drawing
+/// that can be recognized, otherwise the answer itself.
+pub(crate) fn read_answer(shown: &str) -> String {
+    if shown.contains('\n') {
+        if let Some(letters) = recognize(shown) {
+            return letters;
+        }
+    }
+    shown.to_owned()
+}
+
+/// Draws a grid of pixels as text, with `#` for on and `.` for off, in a form that `recognize`
+/// reads. Use this to implement `Answer` for grid types.
+pub fn draw(rows: impl IntoIterator<Item = impl IntoIterator<Item = bool>>) -> String {
+    rows.into_iter()
+        .map(|row| row.into_iter().map(|on| if on { '#' } else { '.' }).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .show()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small() {
+        let drawing = "\
+#..#.####.#....#.....##..
+#..#.#....#....#....#..#.
+####.###..#....#....#..#.
+#..#.#....#....#....#..#.
+#..#.#....#....#....#..#.
+#..#.####.####.####..##..";
+        assert_eq!(recognize(drawing).as_deref(), Some("HELLO"));
+        assert_eq!(recognize(&format!("\n{}\n\n", drawing.replace('#', "█"))).as_deref(), Some("HELLO"));
+    }
+
+    #[test]
+    fn small_narrow_and_wide() {
+        let drawing = "\
+.###.#...#
+..#..#...#
+..#...#.#.
+..#....#..
+..#....#..
+.###...#..";
+        assert_eq!(recognize(drawing).as_deref(), Some("IY"));
+    }
+
+    #[test]
+    fn large() {
+        let drawing = "\
+#....#..######.....###
+#....#..#...........#.
+#....#..#...........#.
+#....#..#...........#.
+######..#####.......#.
+#....#..#...........#.
+#....#..#...........#.
+#....#..#.......#...#.
+#....#..#.......#...#.
+#....#..######...###..";
+        assert_eq!(recognize(drawing).as_deref(), Some("HEJ"));
+    }
+
+    #[test]
+    fn unrecognized() {
+        assert_eq!(recognize("#"), None);
+        assert_eq!(recognize(""), None);
+        assert_eq!(recognize("#..#\n#..#\n####\n#..#\n#..#\n#.##"), None);
+        assert_eq!(read_answer("123"), "123");
+        assert_eq!(read_answer("\n#\n#"), "\n#\n#");
+    }
+
+    #[test]
+    fn drawn() {
+        let drawing = draw(["#..#", "#..#", "####", "#..#", "#..#", "#..#"].map(|row| row.chars().map(|c| c == '#')));
+        assert_eq!(read_answer(&drawing), "H");
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::names::{num_days, parse_bin_name, parse_package_name};
+use crate::{ensure_dir_exists, Config};
+
+static MANIFEST_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+static PACKAGE_YEAR: OnceLock<Option<u32>> = OnceLock::new();
+
+/// Records the `CARGO_MANIFEST_DIR` of the crate containing the solutions, so that data files are
+/// found no matter what the working directory is. The macros call this; only the first call has
+/// any effect.
+pub fn set_manifest_dir(manifest_dir: &str) {
+    let _ = MANIFEST_DIR.set(PathBuf::from(manifest_dir));
+}
+
+/// Records the `CARGO_PKG_NAME` of the crate containing the solutions. Data files of other years
+/// than the one in the name, e.g. of a binary named `2024_03` in `aoc2025`, are always stored in a
+/// year directory, so that they do not mix with the package's own. The macros call this; only the
+/// first call has any effect.
+pub fn set_package_name(package_name: &str) {
+    let _ = PACKAGE_YEAR.set(parse_package_name(package_name));
+}
+
+/// Returns the directory of the crate containing the solutions, or the working directory if it
+/// was never set.
+pub(crate) fn manifest_dir() -> &'static Path {
+    MANIFEST_DIR.get().map_or(Path::new("."), PathBuf::as_path)
+}
+
+/// Returns the name of the binary in `src/bin` for each day of the given year that has one. Names
+/// are recognized like `aoc::day!` does, e.g. `01`, `day1` or `2025_01`; if there are several for
+/// a day, the first in alphabetical order is used.
+pub(crate) fn day_binaries(year: u32) -> Result<BTreeMap<u32, String>, io::Error> {
+    let mut names = Vec::new();
+    for entry in fs::read_dir(manifest_dir().join("src/bin"))? {
+        let file_name = entry?.file_name();
+        if let Some(name) = file_name.to_str().and_then(|name| name.strip_suffix(".rs")) {
+            names.push(name.to_owned());
+        }
+    }
+    names.sort();
+    Ok(days_of_binaries(year, names))
+}
+
+fn days_of_binaries(year: u32, names: Vec<String>) -> BTreeMap<u32, String> {
+    let mut binaries = BTreeMap::new();
+    for name in names {
+        let Some(bin_name) = parse_bin_name(&name) else {
+            continue;
+        };
+        if bin_name.year.is_none_or(|bin_year| bin_year == year) && (1..=num_days(year)).contains(&bin_name.day) {
+            binaries.entry(bin_name.day).or_insert(name);
+        }
+    }
+    binaries
+}
+
+/// Returns the path of a data file, e.g. `inputs/08.in`: `file_name` in the directory `kind` in
+/// the data directory, with a year directory in between if `year_dirs` is configured or the year
+/// is not the package's (e.g. `inputs/2025/08.in`).
+///
+/// If the file does not exist yet, but it does exist in the default layout (without data directory
+/// and year directory) or without year directory, it is moved to the new location first. Files
+/// without year directory belong to the package's year, so they are not moved for other years.
+pub(crate) fn data_file(year: u32, kind: &str, file_name: String) -> PathBuf {
+    let package_year = PACKAGE_YEAR.get().copied().flatten();
+    resolve_data_file(&Config::load(), manifest_dir(), package_year, year, kind, &file_name)
+}
+
+fn resolve_data_file(config: &Config, manifest_dir: &Path, package_year: Option<u32>, year: u32, kind: &str, file_name: &str) -> PathBuf {
+    let path = data_file_in(config, manifest_dir, package_year, year, kind, file_name);
+    if path.exists() || package_year.is_some_and(|package_year| package_year != year) {
+        return path;
+    }
+    let legacy_paths = [
+        config.data_dir_in(manifest_dir).join(kind).join(file_name),
+        manifest_dir.join(kind).join(file_name),
+    ];
+    if let Some(legacy) = legacy_paths.iter().find(|legacy| **legacy != path && legacy.exists()) {
+        match move_file(legacy, &path) {
+            Ok(()) => eprintln!("Moved {} to {}", legacy.display(), path.display()),
+            Err(err) => eprintln!("Failed to move {} to {}: {}", legacy.display(), path.display(), err),
+        }
+    }
+    path
+}
+
+fn data_file_in(config: &Config, manifest_dir: &Path, package_year: Option<u32>, year: u32, kind: &str, file_name: &str) -> PathBuf {
+    let mut path = config.data_dir_in(manifest_dir).join(kind);
+    if config.year_dirs || package_year.is_some_and(|package_year| package_year != year) {
+        path.push(year.to_string());
+    }
+    path.join(file_name)
+}
+
+fn move_file(from: &Path, to: &Path) -> Result<(), io::Error> {
+    ensure_dir_exists(to)?;
+    fs::rename(from, to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout() {
+        let config = Config { data_dir: Some("/data".into()), ..Config::default() };
+        let manifest_dir = Path::new("/crate");
+        assert_eq!(data_file_in(&config, manifest_dir, Some(2025), 2025, "inputs", "08.in"), Path::new("/data/inputs/08.in"));
+        let config = Config { year_dirs: true, ..config };
+        assert_eq!(data_file_in(&config, manifest_dir, Some(2025), 2025, "inputs", "08.in"), Path::new("/data/inputs/2025/08.in"));
+        assert_eq!(data_file_in(&Config::default(), manifest_dir, Some(2025), 2025, "inputs", "08.in"), Path::new("/crate/inputs/08.in"));
+        assert_eq!(data_file_in(&Config::default(), manifest_dir, None, 2025, "inputs", "08.in"), Path::new("/crate/inputs/08.in"));
+    }
+
+    #[test]
+    fn other_year() {
+        let manifest_dir = std::env::temp_dir().join(format!("aoc-test-other-year-{}", std::process::id()));
+        fs::create_dir_all(manifest_dir.join("inputs")).unwrap();
+        fs::write(manifest_dir.join("inputs/03.in"), "2025\n").unwrap();
+
+        let path = resolve_data_file(&Config::default(), &manifest_dir, Some(2025), 2024, "inputs", "03.in");
+        assert_eq!(path, manifest_dir.join("inputs/2024/03.in"));
+        assert!(!path.exists());
+        let path = resolve_data_file(&Config::default(), &manifest_dir, Some(2025), 2025, "inputs", "03.in");
+        assert_eq!(fs::read_to_string(path).unwrap(), "2025\n");
+        fs::remove_dir_all(manifest_dir).unwrap();
+    }
+
+    #[test]
+    fn move_into_data_dir() {
+        let manifest_dir = std::env::temp_dir().join(format!("aoc-test-data-dir-{}", std::process::id()));
+        let data_dir = manifest_dir.join("data");
+        fs::create_dir_all(manifest_dir.join("inputs")).unwrap();
+        fs::write(manifest_dir.join("inputs/08.in"), "1,2,3\n").unwrap();
+
+        let config = Config { data_dir: Some(data_dir.clone()), ..Config::default() };
+        let path = resolve_data_file(&config, &manifest_dir, Some(2025), 2025, "inputs", "08.in");
+        assert_eq!(path, data_dir.join("inputs/08.in"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1,2,3\n");
+        assert!(!manifest_dir.join("inputs/08.in").exists());
+
+        let config = Config { year_dirs: true, ..config };
+        let path = resolve_data_file(&config, &manifest_dir, Some(2025), 2025, "inputs", "08.in");
+        assert_eq!(path, data_dir.join("inputs/2025/08.in"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1,2,3\n");
+        fs::remove_dir_all(manifest_dir).unwrap();
+    }
+
+    #[test]
+    fn binaries() {
+        let names = ["01", "day2", "day2_alt", "2024_03", "2025_04", "all", "aoc", "13"].map(str::to_owned);
+        let binaries = days_of_binaries(2025, names.to_vec());
+        assert_eq!(binaries, BTreeMap::from([(1, "01".to_owned()), (2, "day2".to_owned()), (4, "2025_04".to_owned())]));
+    }
+
+    #[test]
+    fn move_into_year_dir() {
+        let dir = std::env::temp_dir().join(format!("aoc-test-paths-{}", std::process::id()));
+        let from = dir.join("inputs/08.in");
+        let to = dir.join("inputs/2025/08.in");
+        ensure_dir_exists(&from).unwrap();
+        fs::write(&from, "1,2,3\n").unwrap();
+        move_file(&from, &to).unwrap();
+        assert!(!from.exists());
+        assert_eq!(fs::read_to_string(&to).unwrap(), "1,2,3\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,284 @@
+use std::error::Error;
+use std::fs;
+
+use kuchiki::traits::TendrilSink;
+use kuchiki::{parse_html, NodeRef};
+
+use crate::client::send_get_request;
+use crate::{ensure_dir_exists, puzzle_file_name};
+
+/// ANSI escape sequence that resets all styles.
+const RESET: &str = "\x1b[0m";
+
+/// How to render the puzzle description.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Plain text, with ANSI escape codes for emphasis if `color` is set.
+    Terminal { color: bool },
+    Markdown,
+}
+
+/// Fetches the puzzle page from the site and caches it. This always makes a request, so it picks
+/// up part 2 once it is unlocked.
+pub(crate) fn fetch_puzzle_page(year: u32, day: u32) -> Result<String, Box<dyn Error>> {
+    let html = send_get_request(&format!("/{}/day/{}", year, day))?;
+    let file_name = puzzle_file_name(year, day);
+    ensure_dir_exists(&file_name)?;
+    fs::write(&file_name, &html)?;
+    Ok(html)
+}
+
+/// Returns the puzzle page from the cache if it contains both parts, otherwise fetches it.
+pub fn puzzle_page(year: u32, day: u32) -> Result<String, Box<dyn Error>> {
+    match fs::read_to_string(puzzle_file_name(year, day)) {
+        Ok(html) if count_parts(&html) >= 2 => Ok(html),
+        cached => {
+            eprintln!("Puzzle {} day {} not cached or incomplete, fetching...", year, day);
+            fetch_puzzle_page(year, day).or_else(|err| match cached {
+                // Better an incomplete description than none at all, e.g. when offline.
+                Ok(html) => {
+                    eprintln!("Failed to fetch puzzle, showing cached version: {}", err);
+                    Ok(html)
+                }
+                Err(_) => Err(err),
+            })
+        }
+    }
+}
+
+/// Returns whether the cached puzzle page contains both parts, so fetching it again would not
+/// reveal anything new.
+pub(crate) fn is_complete(year: u32, day: u32) -> bool {
+    fs::read_to_string(puzzle_file_name(year, day)).is_ok_and(|html| count_parts(&html) >= 2)
+}
+
+fn count_parts(html: &str) -> usize {
+    parse_html().one(html).select("article.day-desc").map_or(0, |articles| articles.count())
+}
+
+/// Renders the `<article class="day-desc">` sections of a puzzle page, one for each part that is
+/// unlocked.
+pub fn render_puzzle(html: &str, format: Format) -> String {
+    let root = parse_html().one(html);
+    let mut out = String::new();
+    for article in root.select("article.day-desc").into_iter().flatten() {
+        let mut renderer = Renderer { format, out: String::new() };
+        renderer.render_blocks(article.as_node(), 0);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out += renderer.out.trim_end();
+        out.push('\n');
+    }
+    out
+}
+
+struct Renderer {
+    format: Format,
+    out: String,
+}
+
+impl Renderer {
+    fn render_blocks(&mut self, node: &NodeRef, depth: usize) {
+        for child in node.children() {
+            let Some(element) = child.as_element() else {
+                continue;
+            };
+            match &*element.name.local {
+                "h2" => {
+                    let text = collapse_whitespace(&child.text_contents());
+                    match self.format {
+                        Format::Markdown => self.out += &format!("## {}\n\n", text.trim()),
+                        Format::Terminal { .. } => {
+                            let text = self.styled(text.trim(), "\x1b[1;32m");
+                            self.out += &format!("{}\n\n", text);
+                        }
+                    }
+                }
+                "p" => {
+                    let text = self.render_inline(&child);
+                    self.out += &format!("{}\n\n", text.trim());
+                }
+                "pre" => {
+                    let text = child.text_contents();
+                    match self.format {
+                        Format::Markdown => self.out += &format!("```\n{}\n```\n\n", text.trim_end()),
+                        Format::Terminal { .. } => {
+                            for line in text.trim_end().lines() {
+                                self.out += &format!("    {}\n", line);
+                            }
+                            self.out.push('\n');
+                        }
+                    }
+                }
+                "ul" | "ol" => {
+                    self.render_list(&child, depth);
+                    self.out.push('\n');
+                }
+                _ => self.render_blocks(&child, depth),
+            }
+        }
+    }
+
+    fn render_list(&mut self, list: &NodeRef, depth: usize) {
+        let ordered = has_name(list, "ol");
+        for (index, item) in list.children().filter(|item| has_name(item, "li")).enumerate() {
+            let bullet = if ordered { format!("{}.", index + 1) } else { "-".to_owned() };
+            let text = self.render_inline(&item);
+            self.out += &format!("{}{} {}\n", "  ".repeat(depth), bullet, text.trim());
+            for nested in item.children().filter(|nested| has_name(nested, "ul") || has_name(nested, "ol")) {
+                self.render_list(&nested, depth + 1);
+            }
+        }
+    }
+
+    /// Renders the inline content of a block element, skipping nested lists.
+    fn render_inline(&self, node: &NodeRef) -> String {
+        let mut out = String::new();
+        for child in node.children() {
+            if let Some(text) = child.as_text() {
+                out += &collapse_whitespace(&text.borrow());
+                continue;
+            }
+            let Some(element) = child.as_element() else {
+                continue;
+            };
+            let inner = self.render_inline(&child);
+            out += &match (&*element.name.local, self.format) {
+                ("ul" | "ol", _) => String::new(),
+                ("em", Format::Markdown) => format!("**{}**", inner),
+                ("em", Format::Terminal { .. }) => self.styled(&inner, "\x1b[1;97m"),
+                ("code", Format::Markdown) => render_code_markdown(&child),
+                ("code", Format::Terminal { .. }) => self.styled(&inner, "\x1b[36m"),
+                ("a", Format::Markdown) => match element.attributes.borrow().get("href") {
+                    Some(href) => format!("[{}]({})", inner, href),
+                    None => inner,
+                },
+                _ => inner,
+            };
+        }
+        out
+    }
+
+    /// Wraps the text in the escape sequence and a reset. Resets of nested styles in the text are
+    /// followed by the escape sequence again, so that the rest of the text keeps this style.
+    fn styled(&self, text: &str, escape: &str) -> String {
+        match self.format {
+            Format::Terminal { color: true } => {
+                format!("{}{}{}", escape, text.replace(RESET, &format!("{}{}", RESET, escape)), RESET)
+            }
+            _ => text.to_owned(),
+        }
+    }
+}
+
+/// Renders a `<code>` element as Markdown code spans. Markdown does not support emphasis inside a
+/// code span, so emphasized parts become separate code spans that are emphasized as a whole.
+fn render_code_markdown(code: &NodeRef) -> String {
+    let mut out = String::new();
+    let mut span = String::new();
+    for child in code.children() {
+        if has_name(&child, "em") {
+            if !span.is_empty() {
+                out += &format!("`{}`", span);
+                span.clear();
+            }
+            out += &format!("**`{}`**", collapse_whitespace(&child.text_contents()));
+        } else {
+            span += &collapse_whitespace(&child.text_contents());
+        }
+    }
+    if !span.is_empty() {
+        out += &format!("`{}`", span);
+    }
+    out
+}
+
+fn has_name(node: &NodeRef, name: &str) -> bool {
+    node.as_element().is_some_and(|element| &*element.name.local == name)
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last_was_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !last_was_space {
+                out.push(' ');
+            }
+            last_was_space = true;
+        } else {
+            out.push(c);
+            last_was_space = false;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+        <article class="day-desc"><h2>--- Day 3: Lobby ---</h2>
+        <p>You descend a <a href="/2025/day/2">short staircase</a>, enter
+        the lobby.</p>
+        <pre><code>987654321111111
+811111111111119
+</code></pre>
+        <ul>
+        <li>In <code><em>98</em>7654321111111</code>, turn on <em>9</em> and <em>8</em>.</li>
+        <li>Nested:
+          <ul><li>deeper</li></ul>
+        </li>
+        </ul>
+        <p>What is the <em>total output joltage</em>?</p>
+        </article>
+        <p>Your puzzle answer was <code>17321</code>.</p>
+        <article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+        <p>Now use <code>12</code> batteries.</p>
+        </article>
+        </main></body></html>"#;
+
+    #[test]
+    fn markdown() {
+        assert_eq!(render_puzzle(PAGE, Format::Markdown), "\
+## --- Day 3: Lobby ---
+
+You descend a [short staircase](/2025/day/2), enter the lobby.
+
+```
+987654321111111
+811111111111119
+```
+
+- In **`98`**`7654321111111`, turn on **9** and **8**.
+- Nested:
+  - deeper
+
+What is the **total output joltage**?
+
+## --- Part Two ---
+
+Now use `12` batteries.
+");
+    }
+
+    #[test]
+    fn terminal() {
+        let text = render_puzzle(PAGE, Format::Terminal { color: false });
+        assert!(text.starts_with("--- Day 3: Lobby ---\n\nYou descend a short staircase, enter the lobby.\n\n    987654321111111\n"));
+        assert!(text.contains("- In 987654321111111, turn on 9 and 8.\n"));
+        assert!(text.ends_with("--- Part Two ---\n\nNow use 12 batteries.\n"));
+
+        let text = render_puzzle(PAGE, Format::Terminal { color: true });
+        assert!(text.contains("the \x1b[1;97mtotal output joltage\x1b[0m?"));
+        assert!(text.contains("In \x1b[36m\x1b[1;97m98\x1b[0m\x1b[36m7654321111111\x1b[0m, turn"));
+    }
+
+    #[test]
+    fn parts() {
+        assert_eq!(count_parts(PAGE), 2);
+        assert_eq!(count_parts("<html></html>"), 0);
+    }
+}
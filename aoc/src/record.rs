@@ -0,0 +1,259 @@
+use std::time::Duration;
+
+use serde_json::{json, Map};
+
+use crate::answers::{escape, unescape};
+use crate::MemoryStats;
+
+/// The stages that a solution can consist of, in the order in which they run.
+const STAGES: [&str; 4] = ["run", "parse", "part 1", "part 2"];
+
+const CHECKS: [&str; 3] = ["OK", "CHANGED", "NEW"];
+
+/// Start of the error message when the solution did not finish within its time budget.
+pub(crate) const TIMEOUT_ERROR: &str = "timed out after";
+
+/// Column names of the TSV format.
+pub const TSV_HEADER: &str = "year\tday\tinput_checksum\tpart_1\tcheck_1\tpart_2\tcheck_2\ttotal_ns\t\
+    run_ns\tparse_ns\tpart_1_ns\tpart_2_ns\tallocations\tbytes_allocated\tpeak_bytes\terror_stage\terror";
+
+/// How the runner prints its results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    Text,
+    /// One JSON object per day, one per line.
+    Json,
+    /// A header line, followed by one tab-separated line per day.
+    Tsv,
+}
+
+impl OutputFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "text" => Some(OutputFormat::Text),
+            "json" => Some(OutputFormat::Json),
+            "tsv" => Some(OutputFormat::Tsv),
+            _ => None,
+        }
+    }
+}
+
+/// The outcome of running a day's solution, in a form that can be written as JSON or TSV.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    /// The [`checksum`] of the input, or `None` if the solution did not get to run.
+    pub input_checksum: Option<String>,
+    /// Answers to each part. Parts that were not computed, not solved or not printed are `None`.
+    pub answers: Vec<Option<String>>,
+    /// Status of each answer compared to the known answer (`OK`, `CHANGED` or `NEW`), or `None` if
+    /// it was not checked.
+    pub checks: Vec<Option<&'static str>>,
+    /// Name and duration of each stage that was run.
+    pub stages: Vec<(&'static str, Duration)>,
+    pub memory: Option<MemoryStats>,
+    /// The name of the stage that failed, and the error message.
+    pub error: Option<(String, String)>,
+}
+
+impl Record {
+    pub fn total_duration(&self) -> Duration {
+        self.stages.iter().map(|(_, duration)| *duration).sum()
+    }
+
+    /// Returns whether the solution was stopped because it ran out of time.
+    pub fn is_timeout(&self) -> bool {
+        self.error.as_ref().is_some_and(|(_, message)| message.starts_with(TIMEOUT_ERROR))
+    }
+
+    /// Formats the record as a single line of JSON. Durations are in nanoseconds, and answers are
+    /// always strings.
+    pub fn to_json(&self) -> String {
+        let parts = self.answers.iter()
+            .enumerate()
+            .map(|(index, answer)| json!({
+                "part": index + 1,
+                "answer": answer,
+                "check": self.checks.get(index).copied().flatten(),
+            }))
+            .collect::<Vec<_>>();
+        let timings = self.stages.iter()
+            .map(|(stage, duration)| (column_name(stage), json!(duration.as_nanos() as u64)))
+            .collect::<Map<_, _>>();
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "input_checksum": self.input_checksum,
+            "parts": parts,
+            "timings_ns": timings,
+            "total_ns": self.total_duration().as_nanos() as u64,
+            "memory": self.memory.as_ref().map(|memory| json!({
+                "allocations": memory.allocations,
+                "bytes_allocated": memory.bytes_allocated,
+                "peak_bytes": memory.peak_bytes,
+            })),
+            "error": self.error.as_ref().map(|(stage, message)| json!({ "stage": stage, "message": message })),
+        }).to_string()
+    }
+
+    /// Formats the record as a line of TSV with the columns in [`TSV_HEADER`]. Answers and error
+    /// messages are escaped so that they fit on one line.
+    pub fn to_tsv(&self) -> String {
+        let mut fields = vec![self.year.to_string(), self.day.to_string(), self.input_checksum.clone().unwrap_or_default()];
+        for index in 0..2 {
+            fields.push(self.answers.get(index).cloned().flatten().map(|answer| escape(&answer)).unwrap_or_default());
+            fields.push(self.checks.get(index).copied().flatten().unwrap_or_default().to_owned());
+        }
+        fields.push(self.total_duration().as_nanos().to_string());
+        for stage in STAGES {
+            let duration = self.stages.iter().find(|(name, _)| *name == stage).map(|(_, duration)| duration);
+            fields.push(duration.map(|duration| duration.as_nanos().to_string()).unwrap_or_default());
+        }
+        match &self.memory {
+            Some(memory) => fields.extend([memory.allocations, memory.bytes_allocated, memory.peak_bytes].map(|n| n.to_string())),
+            None => fields.extend([String::new(), String::new(), String::new()]),
+        }
+        match &self.error {
+            Some((stage, message)) => fields.extend([stage.clone(), escape(message)]),
+            None => fields.extend([String::new(), String::new()]),
+        }
+        fields.join("\t")
+    }
+
+    /// Parses a line produced by [`Record::to_tsv`].
+    pub fn from_tsv(line: &str) -> Result<Record, String> {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        let [year, day, input_checksum, part_1, check_1, part_2, check_2, _total, run, parse, part_1_ns, part_2_ns,
+            allocations, bytes_allocated, peak_bytes, error_stage, error] = fields[..] else {
+            return Err(format!("expected {} fields, got {}", TSV_HEADER.split('\t').count(), fields.len()));
+        };
+        let number = |field: &str| field.parse::<u64>().map_err(|_| format!("invalid number {:?}", field));
+
+        let mut stages = Vec::new();
+        for (stage, nanos) in STAGES.into_iter().zip([run, parse, part_1_ns, part_2_ns]) {
+            if let Some(nanos) = non_empty(nanos) {
+                stages.push((stage, Duration::from_nanos(number(nanos)?)));
+            }
+        }
+        let mut checks = Vec::new();
+        for check in [check_1, check_2] {
+            checks.push(match non_empty(check) {
+                Some(check) => Some(*CHECKS.iter().find(|&&c| c == check).ok_or_else(|| format!("invalid check {:?}", check))?),
+                None => None,
+            });
+        }
+        let memory = match non_empty(allocations) {
+            Some(allocations) => Some(MemoryStats {
+                allocations: number(allocations)?,
+                bytes_allocated: number(bytes_allocated)?,
+                peak_bytes: number(peak_bytes)?,
+            }),
+            None => None,
+        };
+        Ok(Record {
+            year: number(year)? as u32,
+            day: number(day)? as u32,
+            input_checksum: non_empty(input_checksum).map(str::to_owned),
+            answers: [part_1, part_2].map(|answer| non_empty(answer).map(unescape)).to_vec(),
+            checks,
+            stages,
+            memory,
+            error: non_empty(error_stage).map(|stage| (stage.to_owned(), unescape(error))),
+        })
+    }
+}
+
+/// Returns a checksum of the input, to tell whether it changed: its 64-bit FNV-1a hash in hex.
+pub fn checksum(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3));
+    format!("{:016x}", hash)
+}
+
+fn non_empty(field: &str) -> Option<&str> {
+    Some(field).filter(|field| !field.is_empty())
+}
+
+/// Turns a stage name like `part 1` into a column or key name like `part_1`.
+fn column_name(stage: &str) -> String {
+    stage.replace(' ', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::Value;
+
+    fn record() -> Record {
+        Record {
+            year: 2025,
+            day: 8,
+            input_checksum: Some(checksum("a")),
+            answers: vec![Some("54180".to_owned()), Some("#.\n.#".to_owned())],
+            checks: vec![Some("OK"), None],
+            stages: vec![("parse", Duration::from_nanos(10)), ("part 1", Duration::from_nanos(200)), ("part 2", Duration::from_nanos(3000))],
+            memory: None,
+            error: Some(("part 2".to_owned(), "bad \"input\"".to_owned())),
+        }
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(checksum(""), "cbf29ce484222325");
+        assert_eq!(checksum("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn json() {
+        let json: Value = serde_json::from_str(&record().to_json()).unwrap();
+        assert_eq!(json, json!({
+            "year": 2025,
+            "day": 8,
+            "input_checksum": "af63dc4c8601ec8c",
+            "parts": [{"part": 1, "answer": "54180", "check": "OK"}, {"part": 2, "answer": "#.\n.#", "check": null}],
+            "timings_ns": {"parse": 10, "part_1": 200, "part_2": 3000},
+            "total_ns": 3210,
+            "memory": null,
+            "error": {"stage": "part 2", "message": "bad \"input\""},
+        }));
+    }
+
+    #[test]
+    fn tsv_roundtrip() {
+        let record = record();
+        let line = record.to_tsv();
+        assert_eq!(line.split('\t').count(), TSV_HEADER.split('\t').count());
+        assert_eq!(Record::from_tsv(&line), Ok(record));
+
+        let record = Record {
+            year: 2025,
+            day: 1,
+            answers: vec![None, None],
+            checks: vec![None, None],
+            stages: vec![("run", Duration::from_nanos(5))],
+            memory: Some(MemoryStats { allocations: 1, bytes_allocated: 2, peak_bytes: 3 }),
+            ..Record::default()
+        };
+        assert_eq!(Record::from_tsv(&record.to_tsv()), Ok(record.clone()));
+        assert!(Record::from_tsv("2025\t1").is_err());
+
+        let with_tabs = Record {
+            answers: vec![Some("a\tb\r\n".to_owned()), None],
+            checks: vec![None, None],
+            error: Some(("part 1".to_owned(), "expected\tnumber".to_owned())),
+            ..record
+        };
+        let line = with_tabs.to_tsv();
+        assert_eq!(line.split('\t').count(), TSV_HEADER.split('\t').count());
+        assert_eq!(Record::from_tsv(&line), Ok(with_tabs));
+    }
+
+    #[test]
+    fn timeout() {
+        assert!(!record().is_timeout());
+        let record = Record { error: Some(("part 2".to_owned(), format!("{} 1 s", TIMEOUT_ERROR))), ..record() };
+        assert!(record.is_timeout());
+    }
+}
@@ -0,0 +1,44 @@
+//! Fallbacks for the names that the `#[aoc::parse]` and `#[aoc::part(2)]` attributes define. The
+//! `fn main()` generated by `#[aoc::part(1)]` glob-imports this module, so the fallbacks are only
+//! used when the day file does not register its own.
+
+/// Stands in for the parse function when there is no `#[aoc::parse]`.
+#[allow(non_camel_case_types)]
+pub struct __aoc_parse;
+
+/// Stands in for part 2 when there is no `#[aoc::part(2)]`, which leaves it unsolved.
+pub fn __aoc_part_2<P: ?Sized>(_input: &P) -> Option<u64> {
+    None
+}
+
+/// A function that parses the input into `P`, or a `Result` of it (see [`crate::ParseOutput`]).
+/// Only `__aoc_parse` does not implement this, which makes for a clear error when part 1 takes
+/// parsed input but nothing is registered to parse it.
+#[diagnostic::on_unimplemented(
+    message = "no `#[aoc::parse]` function found",
+    label = "part 1 takes parsed input",
+    note = "add `#[aoc::parse]` to a function that takes the input as `&str`, or make part 1 take `&str`",
+)]
+pub trait ParseFn<P> {
+    fn parse(&self, input: &str) -> P;
+}
+
+impl<P, F: Fn(&str) -> P> ParseFn<P> for F {
+    fn parse(&self, input: &str) -> P {
+        self(input)
+    }
+}
+
+/// Implemented only for `__aoc_parse`, to make it an error to register a parse function when part 1
+/// takes the input as `&str`, which would leave the parse function unused.
+#[diagnostic::on_unimplemented(
+    message = "part 1 takes `&str` but a parse function is registered",
+    label = "the `#[aoc::parse]` function would not be used",
+    note = "make part 1 take the output of the parse function by reference, or remove `#[aoc::parse]`",
+)]
+pub trait NoParseFn {}
+
+impl NoParseFn for __aoc_parse {}
+
+/// Checks that no parse function is registered; see [`NoParseFn`].
+pub fn no_parse_fn<T: NoParseFn>(_parse: &T) {}
@@ -0,0 +1,534 @@
+use std::env;
+use std::hint::black_box;
+use std::io::{self, IsTerminal};
+use std::marker::PhantomData;
+use std::process;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::args::{self, Options, Source};
+use crate::examples::fetch_examples;
+use crate::memory::measure;
+use crate::ocr::read_answer;
+use crate::puzzle::{puzzle_page, render_puzzle, Format};
+use crate::record::{checksum, OutputFormat, Record, TIMEOUT_ERROR, TSV_HEADER};
+use crate::{bench, format_error, submit, Answer, Check, FormatDuration, KnownAnswers, MemoryStats, ParseOutput};
+
+/// Exit code for errors reading the input or talking to the site.
+pub(crate) const EXIT_FAILURE: i32 = 1;
+/// Exit code for invalid command-line arguments.
+pub(crate) const EXIT_USAGE: i32 = 2;
+/// Exit code when an answer differs from the known answer.
+pub(crate) const EXIT_CHANGED: i32 = 3;
+/// Exit code when the solution returned an error.
+pub(crate) const EXIT_SOLUTION_ERROR: i32 = 4;
+/// Exit code when the solution did not finish within the `--timeout`.
+pub(crate) const EXIT_TIMEOUT: i32 = 5;
+
+/// The stages run so far while a [`Watchdog`] is active, or `None` if there is none or the solution
+/// has finished.
+static STAGE_LOG: Mutex<Option<StageLog>> = Mutex::new(None);
+
+/// The stages that have finished, with their durations, and the one that is running, with its start.
+#[derive(Default)]
+struct StageLog {
+    finished: Vec<(&'static str, Duration)>,
+    running: Option<(&'static str, Instant)>,
+}
+
+impl StageLog {
+    /// Returns the duration of each stage so far, counting the running one up to now.
+    fn stages(&self) -> Vec<(&'static str, Duration)> {
+        let running = self.running.map(|(stage, start)| (stage, start.elapsed()));
+        self.finished.iter().copied().chain(running).collect()
+    }
+}
+
+fn stage_log() -> MutexGuard<'static, Option<StageLog>> {
+    STAGE_LOG.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The outcome of running a solution once.
+pub struct Run {
+    /// Answers to each part, in order. Parts that were not computed or not solved are `None`.
+    pub parts: Vec<Option<String>>,
+    /// Name and duration of each stage that was run.
+    pub stages: Vec<(&'static str, Duration)>,
+    /// The first error returned by the solution, with the name of the stage that returned it.
+    pub error: Option<(&'static str, String)>,
+}
+
+impl Run {
+    pub fn total_duration(&self) -> Duration {
+        self.stages.iter().map(|(_, duration)| *duration).sum()
+    }
+}
+
+/// A solution to a puzzle, consisting of one or more separately timed stages.
+pub trait Solution {
+    /// Runs the solution on the given input. If `part` is set, other parts may be skipped.
+    fn run(&self, input: &str, part: Option<usize>) -> Run;
+}
+
+/// A single function that computes the answers to all parts at once.
+pub struct Whole<F>(pub F);
+
+impl<A, F> Solution for Whole<F> where A: Answer, F: Fn(&str) -> A {
+    fn run(&self, input: &str, _part: Option<usize>) -> Run {
+        let (answer, duration) = time("run", || (self.0)(input));
+        Run {
+            parts: answer.parts(),
+            stages: vec![("run", duration)],
+            error: answer.error().map(|message| ("run", message)),
+        }
+    }
+}
+
+/// Separate functions for each part, each taking the raw input.
+pub struct Parts<F1, F2>(pub F1, pub F2);
+
+impl<A, B, F1, F2> Solution for Parts<F1, F2>
+where A: Answer, B: Answer, F1: Fn(&str) -> A, F2: Fn(&str) -> B
+{
+    fn run(&self, input: &str, part: Option<usize>) -> Run {
+        let mut run = Run { parts: vec![None, None], stages: Vec::new(), error: None };
+        run_part(&mut run, part, 1, || (self.0)(input));
+        run_part(&mut run, part, 2, || (self.1)(input));
+        run
+    }
+}
+
+/// A parse function whose output is passed to separate functions for each part. The parse output
+/// cannot borrow from the input. If parsing fails (see [`Parser`]), the parts are not run.
+pub struct Parsed<FP, F1, F2>(pub FP, pub F1, pub F2);
+
+impl<A, B, FP, F1, F2> Solution for Parsed<FP, F1, F2>
+where A: Answer, B: Answer, FP: Parser, F1: Fn(&FP::Output) -> A, F2: Fn(&FP::Output) -> B
+{
+    fn run(&self, input: &str, part: Option<usize>) -> Run {
+        let (parsed, duration) = time("parse", || self.0.parse(input));
+        let mut run = Run { parts: vec![None, None], stages: vec![("parse", duration)], error: None };
+        match parsed {
+            Ok(parsed) => {
+                run_part(&mut run, part, 1, || (self.1)(&parsed));
+                run_part(&mut run, part, 2, || (self.2)(&parsed));
+            }
+            Err(message) => run.error = Some(("parse", message)),
+        }
+        run
+    }
+}
+
+/// The parse stage of [`Parsed`]. Implemented for functions that take the input as `&str`, which
+/// always succeed: if one returns a `Result`, the parts get the `Result`.
+pub trait Parser {
+    type Output;
+
+    fn parse(&self, input: &str) -> Result<Self::Output, String>;
+}
+
+impl<P, F> Parser for F where F: Fn(&str) -> P {
+    type Output = P;
+
+    fn parse(&self, input: &str) -> Result<P, String> {
+        Ok(self(input))
+    }
+}
+
+/// A parse function that returns a [`ParseOutput`], so that the parts get `P` even if it returns a
+/// `Result`, and its error is reported instead.
+pub(crate) struct TryParse<F, P>(pub F, pub PhantomData<fn() -> P>);
+
+impl<F, P, R> Parser for TryParse<F, P> where F: Fn(&str) -> R, R: ParseOutput<P> {
+    type Output = P;
+
+    fn parse(&self, input: &str) -> Result<P, String> {
+        (self.0)(input).into_parsed()
+    }
+}
+
+fn run_part<A: Answer>(run: &mut Run, only_part: Option<usize>, part: usize, f: impl FnOnce() -> A) {
+    if only_part.is_none_or(|p| p == part) {
+        let stage = ["part 1", "part 2"][part - 1];
+        let (answer, duration) = time(stage, f);
+        run.stages.push((stage, duration));
+        match answer.error() {
+            Some(message) => {
+                run.error.get_or_insert((stage, message));
+            }
+            None => run.parts[part - 1] = answer.part(),
+        }
+    }
+}
+
+fn time<T>(stage: &'static str, f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    if let Some(log) = stage_log().as_mut() {
+        log.running = Some((stage, start));
+    }
+    let result = f();
+    let duration = start.elapsed();
+    if let Some(log) = stage_log().as_mut() {
+        log.finished.push((stage, duration));
+        log.running = None;
+    }
+    (result, duration)
+}
+
+/// Exits the process if the solution is still running when the time budget runs out, after calling
+/// a function with the stages that were run and their durations so far, the last one being the one
+/// that was running. Call [`Watchdog::finish`] as soon as the solution returns.
+struct Watchdog {
+    _stop: mpsc::Sender<()>,
+}
+
+impl Watchdog {
+    fn start(budget: Duration, on_timeout: impl FnOnce(Vec<(&'static str, Duration)>) + Send + 'static) -> Self {
+        *stage_log() = Some(StageLog::default());
+        let (stop, stopped) = mpsc::channel();
+        thread::spawn(move || {
+            if stopped.recv_timeout(budget) == Err(RecvTimeoutError::Timeout) {
+                // Holding the lock until the process exits keeps `finish` from returning meanwhile.
+                let log = stage_log();
+                if let Some(log) = log.as_ref() {
+                    on_timeout(log.stages());
+                    process::exit(EXIT_TIMEOUT);
+                }
+            }
+        });
+        Watchdog { _stop: stop }
+    }
+
+    /// Stops the watchdog. Once this returns, it no longer exits the process, even if the budget
+    /// ran out just before.
+    fn finish(self) {
+        *stage_log() = None;
+    }
+}
+
+/// Reports that the solution ran out of time, on stderr and in the requested output format.
+fn report_timeout(year: u32, day: u32, stages: Vec<(&'static str, Duration)>, format: OutputFormat, input_checksum: String) {
+    let stage = stages.last().map_or("run", |&(stage, _)| stage);
+    let elapsed = stages.iter().map(|(_, duration)| *duration).sum();
+    let record = Record {
+        year,
+        day,
+        input_checksum: Some(input_checksum),
+        stages,
+        error: Some((stage.to_owned(), format!("{} {}", TIMEOUT_ERROR, FormatDuration(elapsed)))),
+        ..Record::default()
+    };
+    match format {
+        OutputFormat::Text => {}
+        OutputFormat::Json => println!("{}", record.to_json()),
+        OutputFormat::Tsv => println!("{}\n{}", TSV_HEADER, record.to_tsv()),
+    }
+    eprintln!("Timed out in {} of {} day {} after {}", stage, year, day, FormatDuration(elapsed));
+}
+
+/// Prints the answers in human-readable form. If an answer is a drawing of letters, the letters are
+/// printed, followed by the drawing.
+fn print_answers(year: u32, day: u32, run: &Run, memory: Option<MemoryStats>, answers: &[Option<String>], checks: &[Option<Check>], options: &Options) {
+    let duration = run.total_duration();
+    println!(
+        "Answer to {} day {}{} ({}.{:03} s):",
+        year, day, options.source.describe(), duration.as_secs(), duration.subsec_millis());
+    if run.stages.len() > 1 {
+        let breakdown = run.stages.iter()
+            .map(|(name, duration)| format!("{} {}", name, FormatDuration(*duration)))
+            .collect::<Vec<_>>();
+        println!("Timing: {}", breakdown.join(", "));
+    }
+    if let Some(memory) = memory {
+        println!("Memory: {}", memory);
+    }
+    for (index, ((shown, answer), check)) in run.parts.iter().zip(answers).zip(checks).enumerate() {
+        let part = index + 1;
+        if !options.includes_part(part) {
+            continue;
+        }
+        let (Some(shown), Some(answer)) = (shown, answer) else {
+            if run.error.is_none() {
+                println!("Part {}: unsolved", part);
+            }
+            continue;
+        };
+        match check {
+            Some(check) => println!("Part {}: {} ({})", part, answer, check),
+            None => println!("Part {}: {}", part, answer),
+        }
+        if shown != answer {
+            println!("{}", shown.trim_start_matches('\n'));
+        }
+    }
+}
+
+/// Checks the answers against the known answers. Only answers to the puzzle input are checked, and
+/// only for the parts that are printed.
+fn check_answers(known: &KnownAnswers, answers: &[Option<String>], options: &Options) -> Vec<Option<Check>> {
+    answers.iter()
+        .enumerate()
+        .map(|(index, answer)| {
+            answer.as_ref()
+                .filter(|_| options.source == Source::Input && options.includes_part(index + 1))
+                .map(|answer| known.check(index + 1, answer))
+        })
+        .collect()
+}
+
+/// Collects the results into a record for machine-readable output, without the input checksum and
+/// memory statistics. Parts that were not computed or not printed have no answer and no check
+/// status.
+fn record(year: u32, day: u32, run: &Run, answers: &[Option<String>], checks: &[Option<Check>], options: &Options) -> Record {
+    let answers = answers.iter()
+        .enumerate()
+        .map(|(index, answer)| answer.clone().filter(|_| options.includes_part(index + 1)))
+        .collect::<Vec<_>>();
+    let checks = checks.iter()
+        .zip(&answers)
+        .map(|(check, answer)| check.as_ref().filter(|_| answer.is_some()).map(Check::as_str))
+        .collect();
+    Record {
+        year,
+        day,
+        answers,
+        checks,
+        stages: run.stages.clone(),
+        error: run.error.clone().map(|(stage, message)| (stage.to_owned(), message)),
+        ..Record::default()
+    }
+}
+
+fn print_puzzle(year: u32, day: u32, markdown: bool) {
+    let html = puzzle_page(year, day)
+        .unwrap_or_else(|err| {
+            eprintln!("Failed to get puzzle {} day {}: {}", year, day, format_error(err.as_ref()));
+            process::exit(EXIT_FAILURE);
+        });
+    let format = if markdown {
+        Format::Markdown
+    } else {
+        Format::Terminal { color: io::stdout().is_terminal() }
+    };
+    print!("{}", render_puzzle(&html, format));
+}
+
+/// Parses the command line, runs the solution and reports the results. Exits the process with a
+/// nonzero status on failure.
+pub fn run(year: u32, day: u32, solution: impl Solution) {
+    let options = Options::parse(env::args().skip(1))
+        .unwrap_or_else(|message| {
+            eprintln!("{}\n\n{}", message, args::USAGE);
+            process::exit(EXIT_USAGE);
+        });
+    if options.help {
+        println!("{}", args::USAGE);
+        return;
+    }
+    if options.refresh_examples {
+        match fetch_examples(year, day) {
+            Ok(num_examples) => eprintln!("Refreshed examples for {} day {}: {} in total", year, day, num_examples),
+            Err(err) => {
+                eprintln!("Failed to fetch examples for {} day {}: {}", year, day, format_error(err.as_ref()));
+                process::exit(EXIT_FAILURE);
+            }
+        }
+    }
+    if options.puzzle {
+        print_puzzle(year, day, options.markdown);
+        return;
+    }
+
+    let input = options.source.read(year, day)
+        .unwrap_or_else(|err| {
+            eprintln!("Failed to read input{}: {}", options.source.describe(), format_error(err.as_ref()));
+            process::exit(EXIT_FAILURE);
+        });
+
+    let watchdog = options.timeout.map(|budget| {
+        let (format, input_checksum) = (options.format, checksum(&input));
+        Watchdog::start(budget, move |stages| report_timeout(year, day, stages, format, input_checksum))
+    });
+    let (run, memory) = measure(|| solution.run(&input, options.part));
+    if let Some(watchdog) = watchdog {
+        watchdog.finish();
+    }
+    let answers = run.parts.iter()
+        .map(|shown| shown.as_deref().map(read_answer))
+        .collect::<Vec<_>>();
+    let mut known = KnownAnswers::load(year, day);
+    let checks = check_answers(&known, &answers, &options);
+    let mut changed = checks.iter().any(|check| matches!(check, Some(Check::Changed(_))));
+
+    let record = Record {
+        input_checksum: Some(checksum(&input)),
+        memory,
+        ..record(year, day, &run, &answers, &checks, &options)
+    };
+    match options.format {
+        OutputFormat::Text => print_answers(year, day, &run, memory, &answers, &checks, &options),
+        OutputFormat::Json => println!("{}", record.to_json()),
+        OutputFormat::Tsv => println!("{}\n{}", TSV_HEADER, record.to_tsv()),
+    }
+
+    if let Some((stage, message)) = &run.error {
+        eprintln!("Error in {} of {} day {}: {}", stage, year, day, message);
+        process::exit(EXIT_SOLUTION_ERROR);
+    }
+
+    if let Some(budget) = options.bench {
+        let stats = bench(budget, || {
+            black_box(solution.run(black_box(&input), options.part));
+        });
+        if options.format == OutputFormat::Text {
+            println!("Benchmark: {}", stats);
+        }
+        if let Some(bench_out) = &options.bench_out {
+            if let Err(err) = stats.append_to_file(bench_out, year, day) {
+                eprintln!("Failed to write benchmark results to {}: {}", bench_out.display(), err);
+                process::exit(EXIT_FAILURE);
+            }
+        }
+    }
+
+    if options.accept {
+        for (index, answer) in answers.iter().enumerate() {
+            if let Some(answer) = answer.as_ref().filter(|_| options.includes_part(index + 1)) {
+                known.set(index + 1, answer.clone());
+            }
+        }
+        if let Err(err) = known.save(year, day) {
+            eprintln!("Failed to save known answers: {}", err);
+            process::exit(EXIT_FAILURE);
+        }
+        eprintln!("Saved answers as the known answers");
+        changed = false;
+    }
+
+    if let Some(part) = options.submit {
+        let Some(Some(answer)) = answers.get(part - 1) else {
+            eprintln!("There is no answer to part {} to submit", part);
+            process::exit(EXIT_FAILURE);
+        };
+        eprintln!("Submitting answer {} to part {}...", answer, part);
+        match submit(year, day, part, answer) {
+            // Keep stdout to the record in the machine-readable formats.
+            Ok(verdict) if options.format == OutputFormat::Text => {
+                println!("Answer {} to part {} is {}", answer, part, verdict)
+            }
+            Ok(verdict) => eprintln!("Answer {} to part {} is {}", answer, part, verdict),
+            Err(err) => {
+                eprintln!("{}", format_error(err.as_ref()));
+                process::exit(EXIT_FAILURE);
+            }
+        }
+    }
+
+    if changed {
+        process::exit(EXIT_CHANGED);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage_names(run: &Run) -> Vec<&'static str> {
+        run.stages.iter().map(|(name, _)| *name).collect()
+    }
+
+    #[test]
+    fn whole() {
+        let run = Whole(|input: &str| (input.len(), input.to_owned())).run("abc", None);
+        assert_eq!(run.parts, [Some("3".to_owned()), Some("abc".to_owned())]);
+        assert_eq!(stage_names(&run), ["run"]);
+    }
+
+    #[test]
+    fn parts() {
+        let solution = Parts(|input: &str| input.len(), |input: &str| input.to_owned());
+        let run = solution.run("abc", None);
+        assert_eq!(run.parts, [Some("3".to_owned()), Some("abc".to_owned())]);
+        assert_eq!(stage_names(&run), ["part 1", "part 2"]);
+        let run = solution.run("abc", Some(2));
+        assert_eq!(run.parts, [None, Some("abc".to_owned())]);
+        assert_eq!(stage_names(&run), ["part 2"]);
+    }
+
+    #[test]
+    fn unsolved() {
+        let run = Parts(|input: &str| Some(input.len()), |_: &str| None::<u64>).run("abc", None);
+        assert_eq!(run.parts, [Some("3".to_owned()), None]);
+        assert_eq!(run.error, None);
+        let run = Whole(|input: &str| (input.len(), None::<u64>)).run("abc", None);
+        assert_eq!(run.parts, [Some("3".to_owned()), None]);
+    }
+
+    #[test]
+    fn errors() {
+        let run = Whole(|input: &str| input.parse::<u64>().map(|n| (n, n))).run("x", None);
+        assert!(run.parts.is_empty());
+        assert_eq!(run.error, Some(("run", "invalid digit found in string".to_owned())));
+
+        let solution = Parts(
+            |input: &str| input.len(),
+            |_: &str| -> Result<u64, Box<dyn std::error::Error>> {
+                let cause = "x".parse::<u64>().unwrap_err();
+                Err(format!("bad input: {}", cause).into())
+            });
+        let run = solution.run("abc", None);
+        assert_eq!(run.parts, [Some("3".to_owned()), None]);
+        assert_eq!(run.error, Some(("part 2", "bad input: invalid digit found in string".to_owned())));
+    }
+
+    #[test]
+    fn checks_only_printed_parts() {
+        let mut known = KnownAnswers::default();
+        known.set(1, "3".to_owned());
+        known.set(2, "999".to_owned());
+        let answers = [Some("3".to_owned()), Some("abc".to_owned())];
+        let checks = check_answers(&known, &answers, &Options::default());
+        assert_eq!(checks, [Some(Check::Ok), Some(Check::Changed("999".to_owned()))]);
+        let options = Options { part: Some(1), ..Options::default() };
+        assert_eq!(check_answers(&known, &answers, &options), [Some(Check::Ok), None]);
+        let options = Options { source: Source::Example(0), ..Options::default() };
+        assert_eq!(check_answers(&known, &answers, &options), [None, None]);
+    }
+
+    #[test]
+    fn stage_log() {
+        let log = StageLog { finished: vec![("parse", Duration::from_millis(5))], running: Some(("part 1", Instant::now())) };
+        let stages = log.stages();
+        assert_eq!(stages.iter().map(|(name, _)| *name).collect::<Vec<_>>(), ["parse", "part 1"]);
+        assert_eq!(stages[0].1, Duration::from_millis(5));
+        assert!(StageLog::default().stages().is_empty());
+    }
+
+    #[test]
+    fn parsed() {
+        let solution = Parsed(
+            |input: &str| input.split(',').map(|s| s.parse::<u64>().unwrap()).collect::<Vec<_>>(),
+            |numbers: &Vec<u64>| numbers.iter().sum::<u64>(),
+            |numbers: &Vec<u64>| numbers.iter().product::<u64>());
+        let run = solution.run("2,3,4", None);
+        assert_eq!(run.parts, [Some("9".to_owned()), Some("24".to_owned())]);
+        assert_eq!(stage_names(&run), ["parse", "part 1", "part 2"]);
+    }
+
+    #[test]
+    fn parse_error() {
+        let parse = |input: &str| input.split(',').map(str::parse::<u64>).collect::<Result<Vec<_>, _>>();
+        let solution = Parsed(
+            TryParse(parse, PhantomData),
+            |numbers: &Vec<u64>| numbers.iter().sum::<u64>(),
+            |numbers: &Vec<u64>| numbers.iter().product::<u64>());
+        let run = solution.run("2,3,4", None);
+        assert_eq!(run.parts, [Some("9".to_owned()), Some("24".to_owned())]);
+        let run = solution.run("2,x", None);
+        assert_eq!(run.parts, [None, None]);
+        assert_eq!(stage_names(&run), ["parse"]);
+        assert_eq!(run.error, Some(("parse", "invalid digit found in string".to_owned())));
+    }
+}
@@ -0,0 +1,139 @@
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::time::SystemTime;
+
+use crate::calendar::{is_unlocked, num_days};
+use crate::fetch::fetch_day;
+use crate::paths::manifest_dir;
+use crate::tool::usage_error;
+
+const USAGE: &str = "\
+Usage: aoc new [OPTIONS] DAY
+
+Creates src/bin/DD.rs from a template, and fetches the day's input, examples and puzzle page if it
+is unlocked. Refuses to overwrite an existing file.
+
+Options:
+  --grid             Parse the input into a grid of characters instead of a list of lines
+  -h, --help         Print this help and exit";
+
+const LINES_TEMPLATE: &str = "\
+struct Input {
+    lines: Vec<String>,
+}
+
+fn parse(input: &str) -> Input {
+    Input {
+        lines: input.lines().map(str::to_owned).collect(),
+    }
+}
+
+fn part_1(input: &Input) -> usize {
+    input.lines.len()
+}
+
+fn part_2(input: &Input) -> usize {
+    input.lines.len()
+}
+
+#[test]
+fn test_part_1() {
+    aoc::assert_example!(part_1(&parse(&aoc::example!(0))), 0, 1);
+}
+
+#[test]
+fn test_part_2() {
+    aoc::assert_example!(part_2(&parse(&aoc::example!(0))), 0, 2);
+}
+
+aoc::main!(parse => part_1, part_2);
+";
+
+const GRID_TEMPLATE: &str = "\
+use {crate}::grid::Grid;
+
+fn parse(input: &str) -> Grid<char> {
+    Grid::from_rows(input.lines().map(|line| line.chars()))
+}
+
+fn part_1(grid: &Grid<char>) -> usize {
+    grid.indices().count()
+}
+
+fn part_2(grid: &Grid<char>) -> usize {
+    grid.indices().count()
+}
+
+#[test]
+fn test_part_1() {
+    aoc::assert_example!(part_1(&parse(&aoc::example!(0))), 0, 1);
+}
+
+#[test]
+fn test_part_2() {
+    aoc::assert_example!(part_2(&parse(&aoc::example!(0))), 0, 2);
+}
+
+aoc::main!(parse => part_1, part_2);
+";
+
+/// Runs the `new` command with the given arguments, not including the command name.
+pub fn run_new(year: u32, package_name: &str, args: impl IntoIterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut grid = false;
+    let mut day = None;
+    for arg in args {
+        match arg.as_str() {
+            "--grid" => grid = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => match arg.parse() {
+                Ok(value) if day.is_none() => day = Some(value),
+                _ => usage_error(USAGE, &format!("unexpected argument {}", arg)),
+            },
+        }
+    }
+    let Some(day) = day else {
+        usage_error(USAGE, "missing day");
+    };
+    if !(1..=num_days(year)).contains(&day) {
+        usage_error(USAGE, &format!("day must be between 1 and {} for {}", num_days(year), year));
+    }
+
+    let file_name = manifest_dir().join(format!("src/bin/{:02}.rs", day));
+    let template = if grid { GRID_TEMPLATE } else { LINES_TEMPLATE };
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&file_name)
+        .map_err(|err| format!("could not create {}: {}", file_name.display(), err))?
+        .write_all(render_template(template, package_name).as_bytes())?;
+    println!("Created {}", file_name.display());
+
+    if is_unlocked(year, day, SystemTime::now()) {
+        fetch_day(year, day)?;
+    } else {
+        println!("Day {} is not unlocked yet; run `aoc fetch --wait` to fetch it when it is", day);
+    }
+    Ok(())
+}
+
+fn render_template(template: &str, package_name: &str) -> String {
+    template.replace("{crate}", &package_name.replace('-', "_"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn templates() {
+        assert!(render_template(GRID_TEMPLATE, "aoc-2025").starts_with("use aoc_2025::grid::Grid;\n"));
+        assert_eq!(render_template(LINES_TEMPLATE, "aoc-2025"), LINES_TEMPLATE);
+        for template in [LINES_TEMPLATE, GRID_TEMPLATE] {
+            assert!(template.ends_with("aoc::main!(parse => part_1, part_2);\n"));
+        }
+    }
+}
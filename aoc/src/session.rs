@@ -0,0 +1,117 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use crate::paths::manifest_dir;
+use crate::{ensure_dir_exists, Config};
+
+/// Name of the session cookie file, in the manifest directory or the per-user config directory.
+const COOKIE_FILE_NAME: &str = ".session_cookie";
+
+/// Loads the session cookie from, in order:
+///
+/// - the `AOC_SESSION` environment variable,
+/// - `.session_cookie` in the manifest directory of the crate containing the solutions,
+/// - `session_cookie` in the per-user config directory (e.g. `~/.config/aoc/`).
+///
+/// If none of these is set and stdin is a terminal, prompts for the cookie and saves it in the
+/// per-user config directory, readable only by the user. Otherwise, returns an error rather than
+/// waiting for input that will never come.
+pub fn load_session_cookie(config: &Config) -> Result<String, Box<dyn Error>> {
+    if let Some(cookie) = env::var("AOC_SESSION").ok().filter(|cookie| !cookie.trim().is_empty()) {
+        return Ok(cookie.trim().to_owned());
+    }
+    for file_name in [Some(manifest_dir().join(COOKIE_FILE_NAME)), user_cookie_file_name()].into_iter().flatten() {
+        if let Ok(cookie) = fs::read_to_string(&file_name) {
+            return Ok(cookie.trim().to_owned());
+        }
+    }
+
+    let Some(file_name) = user_cookie_file_name() else {
+        return Err("no session cookie found; set the AOC_SESSION environment variable".into());
+    };
+    if !io::stdin().is_terminal() {
+        return Err(format!(
+            "no session cookie found; set the AOC_SESSION environment variable or put the cookie in {}",
+            file_name.display()).into());
+    }
+    eprintln!("No session cookie found. Please log in to {}/ in your browser, open the browser console, copy the value of the 'session' cookie, and paste it here:", config.base_url);
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    let cookie = line.trim().to_owned();
+    write_private_file(&file_name, &cookie)?;
+    eprintln!("Session cookie saved to {}", file_name.display());
+    Ok(cookie)
+}
+
+/// Returns the error to report when the site rejects the session cookie.
+pub fn invalid_session_error() -> Box<dyn Error> {
+    let location = user_cookie_file_name()
+        .map_or_else(|| COOKIE_FILE_NAME.to_owned(), |file_name| file_name.display().to_string());
+    format!(
+        "the session cookie is invalid or expired; log in again and update the AOC_SESSION \
+        environment variable or {}", location).into()
+}
+
+/// Returns the directory for per-user configuration: `$XDG_CONFIG_HOME/aoc`, `~/.config/aoc`, or
+/// `%APPDATA%\aoc` on Windows.
+pub fn user_config_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()).map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+    Some(base.join("aoc"))
+}
+
+fn user_cookie_file_name() -> Option<PathBuf> {
+    Some(user_config_dir()?.join("session_cookie"))
+}
+
+/// Writes a file that only the current user can read and write.
+fn write_private_file(file_name: &Path, contents: &str) -> Result<(), io::Error> {
+    ensure_dir_exists(file_name)?;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // The mode only applies when the file is created, so also fix up existing files.
+        if file_name.exists() {
+            fs::set_permissions(file_name, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    options.open(file_name)?.write_all(contents.as_bytes())
+}
+
+/// Returns whether a page from the site is the logged-out version, which the site serves instead
+/// of an error when the session cookie is invalid.
+pub fn is_logged_out_page(body: &str) -> bool {
+    body.contains("/auth/login\"") && !body.contains("/auth/logout")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logged_out_page() {
+        assert!(is_logged_out_page(r#"<div><a href="/2025/auth/login">[Log In]</a></div>"#));
+        assert!(!is_logged_out_page(r#"<div class="user">Someone <span class="star-count">4*</span></div><a href="/2025/auth/logout">[Log Out]</a>"#));
+        assert!(!is_logged_out_page("1,2,3\n"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn private_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let file_name = env::temp_dir().join(format!("aoc-test-{}/session_cookie", std::process::id()));
+        write_private_file(&file_name, "abc").unwrap();
+        write_private_file(&file_name, "def").unwrap();
+        assert_eq!(fs::read_to_string(&file_name).unwrap(), "def");
+        assert_eq!(fs::metadata(&file_name).unwrap().permissions().mode() & 0o777, 0o600);
+        fs::remove_dir_all(file_name.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::time::SystemTime;
+
+use kuchiki::traits::TendrilSink;
+use kuchiki::{parse_html, NodeRef};
+
+use crate::calendar::{is_unlocked, num_days};
+use crate::client::send_get_request;
+use crate::paths::day_binaries;
+use crate::table::format_table;
+use crate::tool::usage_error;
+use crate::{example_file_name, format_error, input_file_name, KnownAnswers};
+
+const USAGE: &str = "\
+Usage: aoc status [OPTIONS]
+
+Shows the stars earned on each day according to the site's calendar page, next to what the
+workspace has for that day: a binary in src/bin, the cached input, examples and known answers.
+Flags days where the known answers do not match the stars. Without a connection to the site, only
+the workspace is shown.
+
+Options:
+  -h, --help         Print this help and exit";
+
+/// Runs the `status` command with the given arguments, not including the command name.
+pub fn run_status(year: u32, args: impl IntoIterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    if let Some(arg) = args.into_iter().next() {
+        if arg == "-h" || arg == "--help" {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        usage_error(USAGE, &format!("unexpected argument {}", arg));
+    }
+
+    let stars = match send_get_request(&format!("/{}", year)) {
+        Ok(html) => Some(parse_calendar(&parse_html().one(html), year)),
+        Err(err) => {
+            eprintln!("Failed to fetch the calendar, showing only the workspace: {}", format_error(err.as_ref()));
+            None
+        }
+    };
+    let binaries = day_binaries(year)?;
+    let days = (1..=num_days(year))
+        .map(|day| DayStatus::load(year, day, binaries.contains_key(&day)))
+        .collect::<Vec<_>>();
+    print!("{}", format_status(year, stars.as_ref(), &days, SystemTime::now()));
+    Ok(())
+}
+
+/// Returns the number of stars earned on each day that is listed on a calendar page.
+fn parse_calendar(root: &NodeRef, year: u32) -> BTreeMap<u32, u32> {
+    let prefix = format!("/{}/day/", year);
+    let mut stars = BTreeMap::new();
+    for link in root.select("a").into_iter().flatten() {
+        let attributes = link.attributes.borrow();
+        let Some(day) = attributes.get("href")
+            .and_then(|href| href.strip_prefix(&prefix))
+            .and_then(|day| day.parse::<u32>().ok()) else {
+            continue;
+        };
+        let classes = attributes.get("class").unwrap_or_default().split_whitespace().collect::<Vec<_>>();
+        let label = attributes.get("aria-label").unwrap_or_default();
+        let count = if classes.contains(&"calendar-verycomplete") || label.ends_with("two stars") {
+            2
+        } else if classes.contains(&"calendar-complete") || label.ends_with("one star") {
+            1
+        } else {
+            0
+        };
+        stars.insert(day, count);
+    }
+    stars
+}
+
+/// What the workspace has for a day.
+#[derive(Debug, PartialEq)]
+struct DayStatus {
+    day: u32,
+    binary: bool,
+    input: bool,
+    examples: usize,
+    known_answers: usize,
+}
+
+impl DayStatus {
+    fn load(year: u32, day: u32, binary: bool) -> Self {
+        let known = KnownAnswers::load(year, day);
+        DayStatus {
+            day,
+            binary,
+            input: input_file_name(year, day).exists(),
+            examples: (0..).take_while(|&index| example_file_name(year, day, index).exists()).count(),
+            known_answers: (1..=2).filter(|&part| known.get(part).is_some()).count(),
+        }
+    }
+}
+
+/// Formats a table with a line per day. `stars` is `None` if the calendar could not be fetched.
+fn format_status(year: u32, stars: Option<&BTreeMap<u32, u32>>, days: &[DayStatus], now: SystemTime) -> String {
+    let header = ["Day", "Stars", "Binary", "Input", "Examples", "Answers", "Notes"].map(str::to_owned);
+    let mut lines = vec![header];
+    for status in days {
+        let unlocked = is_unlocked(year, status.day, now);
+        let day_stars = stars.map(|stars| stars.get(&status.day).copied().unwrap_or(0));
+        let mut notes = Vec::new();
+        if !unlocked {
+            notes.push("locked");
+        }
+        match day_stars {
+            Some(day_stars) if (status.known_answers as u32) < day_stars => notes.push("answers not recorded"),
+            Some(day_stars) if (status.known_answers as u32) > day_stars => notes.push("answers not submitted"),
+            _ => {}
+        }
+        if status.binary && !status.input && unlocked {
+            notes.push("input not fetched");
+        }
+        let yes_no = |value: bool| if value { "yes" } else { "-" }.to_owned();
+        lines.push([
+            status.day.to_string(),
+            day_stars.map_or("?".to_owned(), |count| "*".repeat(count as usize)),
+            yes_no(status.binary),
+            yes_no(status.input),
+            status.examples.to_string(),
+            status.known_answers.to_string(),
+            notes.join(", "),
+        ]);
+    }
+    let mut out = match stars {
+        Some(stars) => format!("Advent of Code {}: {} stars\n", year, stars.values().sum::<u32>()),
+        None => format!("Advent of Code {}\n", year),
+    };
+    out += &format_table(&lines, &[1]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::calendar::unlock_time;
+
+    const CALENDAR: &str = include_str!("../testdata/calendar.html");
+
+    #[test]
+    fn calendar() {
+        let stars = parse_calendar(&parse_html().one(CALENDAR), 2025);
+        assert_eq!(stars, BTreeMap::from([(1, 2), (2, 1), (3, 0)]));
+        assert!(parse_calendar(&parse_html().one(CALENDAR), 2024).is_empty());
+    }
+
+    #[test]
+    fn table() {
+        let stars = parse_calendar(&parse_html().one(CALENDAR), 2025);
+        let day = |day, binary, input, examples, known_answers| DayStatus { day, binary, input, examples, known_answers };
+        let days = [day(1, true, true, 1, 2), day(2, true, false, 2, 0), day(3, false, false, 0, 1), day(4, false, false, 0, 0)];
+        assert_eq!(
+            format_status(2025, Some(&stars), &days, unlock_time(2025, 3)),
+            "Advent of Code 2025: 3 stars\n\
+            Day | Stars | Binary | Input | Examples | Answers | Notes\n\
+            ----+-------+--------+-------+----------+---------+----------------------------------------\n\
+            1   | **    | yes    | yes   | 1        | 2       |\n\
+            2   | *     | yes    | -     | 2        | 0       | answers not recorded, input not fetched\n\
+            3   |       | -      | -     | 0        | 1       | answers not submitted\n\
+            4   |       | -      | -     | 0        | 0       | locked\n");
+        assert!(format_status(2025, None, &days[..1], unlock_time(2025, 3)).starts_with("Advent of Code 2025\n"));
+    }
+}
@@ -0,0 +1,297 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use kuchiki::parse_html;
+use kuchiki::traits::TendrilSink;
+
+use crate::client::{send_post_request, ReadError};
+use crate::{ensure_dir_exists, submissions_file_name, KnownAnswers};
+
+/// The site's response to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, but the site did not say in which direction.
+    Wrong,
+    /// Too many recent attempts; we need to wait this long before trying again.
+    RateLimited(Duration),
+    /// The part was already solved, or is not unlocked yet.
+    WrongLevel,
+    /// The answer reached the site, but its response could not be read or understood.
+    Unknown,
+}
+
+impl Verdict {
+    /// Parses the text of the `<article>` in the response page.
+    fn parse(text: &str) -> Option<Self> {
+        if text.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if text.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else if text.contains("That's not the right answer") {
+            Some(Verdict::Wrong)
+        } else if text.contains("You gave an answer too recently") {
+            Some(Verdict::RateLimited(parse_wait_time(text).unwrap_or_default()))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited(_) => "rate_limited",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        Some(match s {
+            "correct" => Verdict::Correct,
+            "too_high" => Verdict::TooHigh,
+            "too_low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            "rate_limited" => Verdict::RateLimited(Duration::ZERO),
+            "wrong_level" => Verdict::WrongLevel,
+            "unknown" => Verdict::Unknown,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct!"),
+            Verdict::TooHigh => write!(f, "wrong, too high"),
+            Verdict::TooLow => write!(f, "wrong, too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited(wait) => write!(f, "rate limited, try again in {} s", wait.as_secs()),
+            Verdict::WrongLevel => write!(f, "not accepted, part already solved or still locked"),
+            Verdict::Unknown => write!(f, "unknown, the response could not be understood"),
+        }
+    }
+}
+
+/// Parses e.g. "You have 1m 25s left to wait." into a duration.
+fn parse_wait_time(text: &str) -> Option<Duration> {
+    let rest = &text[text.find("You have ")? + "You have ".len()..];
+    let rest = &rest[..rest.find(" left to wait")?];
+    let mut secs = 0;
+    for token in rest.split_whitespace() {
+        let (number, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+        let number = number.parse::<u64>().ok()?;
+        secs += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+/// A previous submission, as recorded in the submissions file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Attempt {
+    timestamp: u64,
+    part: usize,
+    verdict: Verdict,
+    answer: String,
+}
+
+impl Attempt {
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(4, '\t');
+        Some(Self {
+            timestamp: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            verdict: Verdict::from_str(fields.next()?)?,
+            answer: fields.next()?.to_owned(),
+        })
+    }
+
+    fn to_line(&self) -> String {
+        format!("{}\t{}\t{}\t{}\n", self.timestamp, self.part, self.verdict.as_str(), self.answer)
+    }
+}
+
+fn load_attempts(year: u32, day: u32) -> Vec<Attempt> {
+    fs::read_to_string(submissions_file_name(year, day))
+        .map(|contents| contents.lines().filter_map(Attempt::parse).collect())
+        .unwrap_or_default()
+}
+
+fn record_attempt(year: u32, day: u32, attempt: &Attempt) -> Result<(), Box<dyn Error>> {
+    let file_name = submissions_file_name(year, day);
+    let mut contents = fs::read_to_string(&file_name).unwrap_or_default();
+    contents.push_str(&attempt.to_line());
+    ensure_dir_exists(&file_name)?;
+    fs::write(&file_name, contents)?;
+    Ok(())
+}
+
+/// Checks the answer against previous attempts, returning a reason if it should not be submitted.
+fn check_attempts(attempts: &[Attempt], part: usize, answer: &str) -> Result<(), String> {
+    let value = answer.parse::<i128>().ok();
+    for attempt in attempts.iter().filter(|attempt| attempt.part == part) {
+        let previous = attempt.answer.parse::<i128>().ok();
+        match attempt.verdict {
+            Verdict::Correct if attempt.answer == answer => {
+                return Err(format!("{} was already accepted as the correct answer", answer));
+            }
+            Verdict::Correct => {
+                return Err(format!("part {} was already solved with answer {}", part, attempt.answer));
+            }
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong if attempt.answer == answer => {
+                return Err(format!("{} was already rejected ({})", answer, attempt.verdict));
+            }
+            Verdict::TooHigh => {
+                if let (Some(value), Some(previous)) = (value, previous) {
+                    if value >= previous {
+                        return Err(format!("{} is not lower than {}, which was too high", answer, previous));
+                    }
+                }
+            }
+            Verdict::TooLow => {
+                if let (Some(value), Some(previous)) = (value, previous) {
+                    if value <= previous {
+                        return Err(format!("{} is not higher than {}, which was too low", answer, previous));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Submits the answer to the given part, unless previous attempts show that it is wrong. Every
+/// attempt that reaches the site is recorded in the submissions file, and correct answers are
+/// saved as known answers.
+/// Finds the verdict in the response page.
+fn parse_response(body: &str) -> Result<Verdict, String> {
+    let root = parse_html().one(body);
+    let text = root.select_first("article")
+        .map_err(|()| "no <article> found in response")?
+        .text_contents();
+    Verdict::parse(&text).ok_or_else(|| format!("could not understand response: {}", text.trim()))
+}
+
+pub fn submit(year: u32, day: u32, part: usize, answer: &str) -> Result<Verdict, Box<dyn Error>> {
+    if answer.is_empty() || answer.contains('\n') {
+        return Err(format!("refusing to submit {:?}, it does not look like an answer", answer).into());
+    }
+    check_attempts(&load_attempts(year, day), part, answer)
+        .map_err(|reason| format!("not submitting: {}", reason))?;
+
+    let path = format!("/{}/day/{}/answer", year, day);
+    let level = part.to_string();
+    let record = |verdict: &Verdict| -> Result<(), Box<dyn Error>> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        record_attempt(year, day, &Attempt { timestamp, part, verdict: verdict.clone(), answer: answer.to_owned() })
+    };
+    // Once the site has the answer, the attempt counts whether or not we understand the response,
+    // so it is recorded as unknown rather than lost.
+    let body = match send_post_request(&path, &[("level", &level), ("answer", answer)]) {
+        Ok(body) => body,
+        Err(err) => {
+            if err.is::<ReadError>() {
+                record(&Verdict::Unknown)?;
+            }
+            return Err(err);
+        }
+    };
+    let verdict = match parse_response(&body) {
+        Ok(verdict) => verdict,
+        Err(err) => {
+            record(&Verdict::Unknown)?;
+            return Err(err.into());
+        }
+    };
+
+    record(&verdict)?;
+    if verdict == Verdict::Correct {
+        let mut known = KnownAnswers::load(year, day);
+        known.set(part, answer.to_owned());
+        known.save(year, day)?;
+    }
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(part: usize, verdict: Verdict, answer: &str) -> Attempt {
+        Attempt { timestamp: 0, part, verdict, answer: answer.to_owned() }
+    }
+
+    #[test]
+    fn parse_verdict() {
+        assert_eq!(
+            Verdict::parse("That's the right answer!  You are one gold star closer to saving Christmas."),
+            Some(Verdict::Correct));
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too high.  If you're stuck, ..."),
+            Some(Verdict::TooHigh));
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too low."),
+            Some(Verdict::TooLow));
+        assert_eq!(
+            Verdict::parse("That's not the right answer.  If you're stuck, ..."),
+            Some(Verdict::Wrong));
+        assert_eq!(
+            Verdict::parse("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 25s left to wait. [Return to Day 8]"),
+            Some(Verdict::RateLimited(Duration::from_secs(85))));
+        assert_eq!(
+            Verdict::parse("You don't seem to be solving the right level.  Did you already complete it?"),
+            Some(Verdict::WrongLevel));
+        assert_eq!(Verdict::parse("Something else entirely"), None);
+        assert_eq!(parse_response("<main><article><p>That's not the right answer.</p></article></main>"), Ok(Verdict::Wrong));
+        assert!(parse_response("<main><p>Something else entirely</p></main>").is_err());
+        assert!(parse_response("<main><article><p>Something else entirely</p></article></main>").is_err());
+    }
+
+    #[test]
+    fn attempt_roundtrip() {
+        let a = attempt(2, Verdict::TooLow, "1234");
+        assert_eq!(Attempt::parse(a.to_line().trim_end()), Some(a));
+        let a = attempt(1, Verdict::Unknown, "77");
+        assert_eq!(Attempt::parse(a.to_line().trim_end()), Some(a));
+    }
+
+    #[test]
+    fn guard() {
+        let attempts = [
+            attempt(1, Verdict::TooHigh, "1000"),
+            attempt(1, Verdict::TooLow, "100"),
+            attempt(1, Verdict::Wrong, "abc"),
+            attempt(1, Verdict::RateLimited(Duration::ZERO), "500"),
+            attempt(1, Verdict::Unknown, "600"),
+            attempt(2, Verdict::Correct, "42"),
+        ];
+        assert!(check_attempts(&attempts, 1, "500").is_ok());
+        assert!(check_attempts(&attempts, 1, "600").is_ok());
+        assert!(check_attempts(&attempts, 1, "1000").is_err());
+        assert!(check_attempts(&attempts, 1, "1001").is_err());
+        assert!(check_attempts(&attempts, 1, "100").is_err());
+        assert!(check_attempts(&attempts, 1, "-5").is_err());
+        assert!(check_attempts(&attempts, 1, "abc").is_err());
+        assert!(check_attempts(&attempts, 1, "abd").is_ok());
+        assert!(check_attempts(&attempts, 2, "42").is_err());
+        assert!(check_attempts(&attempts, 2, "43").is_err());
+    }
+}
@@ -0,0 +1,31 @@
+/// Formats lines of cells as a table, with the columns aligned and separated by `|`. A horizontal
+/// rule is drawn above each line whose index is in `rules`.
+pub(crate) fn format_table<const N: usize>(lines: &[[String; N]], rules: &[usize]) -> String {
+    let widths = (0..N)
+        .map(|col| lines.iter().map(|line| line[col].chars().count()).max().unwrap_or(0))
+        .collect::<Vec<_>>();
+    let mut out = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        if rules.contains(&index) {
+            out += &widths.iter().map(|&width| "-".repeat(width)).collect::<Vec<_>>().join("-+-");
+            out.push('\n');
+        }
+        let cells = line.iter().zip(&widths)
+            .map(|(cell, &width)| format!("{}{}", cell, " ".repeat(width - cell.chars().count())))
+            .collect::<Vec<_>>();
+        out += cells.join(" | ").trim_end();
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table() {
+        let lines = [["Day", "Time"], ["1", "1 ms"], ["Total", ""]].map(|line| line.map(str::to_owned));
+        assert_eq!(format_table(&lines, &[1, 2]), "Day   | Time\n------+-----\n1     | 1 ms\n------+-----\nTotal |\n");
+    }
+}
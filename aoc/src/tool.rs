@@ -0,0 +1,64 @@
+use std::env;
+use std::error::Error;
+use std::process;
+
+use crate::fetch::run_fetch;
+use crate::leaderboard::run_leaderboard;
+use crate::runner::{EXIT_FAILURE, EXIT_USAGE};
+use crate::scaffold::run_new;
+use crate::status::run_status;
+use crate::{format_error, set_manifest_dir, set_package_name};
+
+const USAGE: &str = "\
+Usage: aoc COMMAND [OPTIONS]
+
+Commands:
+  fetch              Download inputs, examples and puzzle pages for all unlocked days
+  new DAY            Create a day's binary from a template and fetch its input
+  status             Show the stars earned on each day, and what the workspace has for it
+  leaderboard ID     Print the standings and star times of a private leaderboard
+
+Pass --help after a command for its options.";
+
+/// Generates a `fn main()` for the `aoc` tool, which bundles commands that are not about a single
+/// day's solution. Put this in `src/bin/aoc.rs`.
+#[macro_export]
+macro_rules! tool {
+    () => {
+        fn main() {
+            $crate::run_tool($crate::year!(), env!("CARGO_MANIFEST_DIR"), env!("CARGO_PKG_NAME"));
+        }
+    }
+}
+
+/// Runs the command given on the command line. Exits with a nonzero status on failure.
+pub fn run_tool(year: u32, manifest_dir: &str, package_name: &str) {
+    set_manifest_dir(manifest_dir);
+    set_package_name(package_name);
+    let mut args = env::args().skip(1);
+    let result: Result<(), Box<dyn Error>> = match args.next().as_deref() {
+        Some("fetch") => run_fetch(year, args),
+        Some("new") => run_new(year, package_name, args),
+        Some("status") => run_status(year, args),
+        Some("leaderboard") => run_leaderboard(year, args),
+        Some("-h" | "--help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => usage_error(USAGE, &format!("unknown command {}", command)),
+        None => {
+            eprintln!("{}", USAGE);
+            process::exit(EXIT_USAGE);
+        }
+    };
+    if let Err(err) = result {
+        eprintln!("{}", format_error(err.as_ref()));
+        process::exit(EXIT_FAILURE);
+    }
+}
+
+/// Prints the message and the usage of a command, and exits with the status for usage errors.
+pub(crate) fn usage_error(usage: &str, message: &str) -> ! {
+    eprintln!("{}\n\n{}", message, usage);
+    process::exit(EXIT_USAGE);
+}
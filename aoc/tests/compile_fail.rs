@@ -0,0 +1,7 @@
+//! Checks the errors that the macros report for invalid uses. Run with `TRYBUILD=overwrite` to
+//! update the expected output after changing a message.
+
+#[test]
+fn compile_fail() {
+    trybuild::TestCases::new().compile_fail("tests/compile_fail/*.rs");
+}
@@ -0,0 +1,11 @@
+#[aoc::part(2)]
+fn first(input: &str) -> usize {
+    input.len()
+}
+
+#[aoc::part(2)]
+fn second(input: &str) -> usize {
+    input.len() * 2
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+fn run(input: &str) -> (usize, usize) {
+    (input.len(), 0)
+}
+
+aoc::example_tests!(run, 0 => (1, 2, 3));
+
+fn main() {}
+
+aoc::example_tests!(run);
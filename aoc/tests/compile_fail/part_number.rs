@@ -0,0 +1,11 @@
+#[aoc::part(3)]
+fn part_3(input: &str) -> usize {
+    input.len()
+}
+
+#[aoc::part(2, day = 3)]
+fn part_2(input: &str) -> usize {
+    input.len()
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+#[aoc::part(1)]
+fn by_value(input: String) -> usize {
+    input.len()
+}
+
+#[aoc::part(2)]
+fn no_answer(_input: &str) {}
+
+#[aoc::parse]
+fn parse_lines(input: &[u8]) -> usize {
+    input.len()
+}
+
+fn main() {}
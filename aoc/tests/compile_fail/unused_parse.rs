@@ -0,0 +1,9 @@
+#[aoc::parse]
+fn parse(input: &str) -> Vec<u64> {
+    input.lines().map(|line| line.parse().unwrap()).collect()
+}
+
+#[aoc::part(1, year = 2025, day = 1)]
+fn part_1(input: &str) -> usize {
+    input.len()
+}
@@ -1,20 +1,23 @@
-fn run(input: &str) -> (u64, u64) {
+fn run(input: &str) -> Result<(u64, u64), String> {
     let mut password_1 = 0;
     let mut password_2 = 0;
     let mut dial = 50_u64;
     for line in input.lines() {
         let [head, tail @ ..] = line.as_bytes() else {
-            panic!("{line}");
+            return Err("empty line in input".to_owned());
         };
         let left = match head {
             b'L' => true,
             b'R' => false,
-            other => panic!("{other}"),
+            _ => return Err(format!("invalid direction in line {line:?}")),
         };
         if left {
             dial = (100 - dial).rem_euclid(100);
         }
-        dial += std::str::from_utf8(tail).unwrap().parse::<u64>().unwrap();
+        dial += std::str::from_utf8(tail)
+            .unwrap()
+            .parse::<u64>()
+            .map_err(|err| format!("invalid distance in line {line:?}: {err}"))?;
         password_2 += dial / 100;
         dial = dial.rem_euclid(100);
         if dial == 0 {
@@ -24,34 +27,41 @@ fn run(input: &str) -> (u64, u64) {
             dial = (100 - dial).rem_euclid(100);
         }
     }
-    (password_1, password_2)
+    Ok((password_1, password_2))
 }
 
 aoc::main!(run);
 
 #[test]
 fn part_1() {
-    assert_eq!(run("R50").0, 1);
-    assert_eq!(run("L50").0, 1);
-    assert_eq!(run("L50\nR100").0, 2);
-    assert_eq!(run("L50\nL100").0, 2);
-    assert_eq!(run("L50\nR199").0, 1);
-    assert_eq!(run("L50\nL199").0, 1);
-    assert_eq!(run("L50\nR200").0, 2);
-    assert_eq!(run("L50\nL200").0, 2);
+    assert_eq!(run("R50").unwrap().0, 1);
+    assert_eq!(run("L50").unwrap().0, 1);
+    assert_eq!(run("L50\nR100").unwrap().0, 2);
+    assert_eq!(run("L50\nL100").unwrap().0, 2);
+    assert_eq!(run("L50\nR199").unwrap().0, 1);
+    assert_eq!(run("L50\nL199").unwrap().0, 1);
+    assert_eq!(run("L50\nR200").unwrap().0, 2);
+    assert_eq!(run("L50\nL200").unwrap().0, 2);
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(run("R1000").1, 10);
-    assert_eq!(run("R50").1, 1);
-    assert_eq!(run("L50").1, 1);
-    assert_eq!(run("L50\nR100").1, 2);
-    assert_eq!(run("L50\nL100").1, 2);
-    assert_eq!(run("L50\nR199").1, 2);
-    assert_eq!(run("L50\nL199").1, 2);
-    assert_eq!(run("L50\nR200").1, 3);
-    assert_eq!(run("L50\nL200").1, 3);
+    assert_eq!(run("R1000").unwrap().1, 10);
+    assert_eq!(run("R50").unwrap().1, 1);
+    assert_eq!(run("L50").unwrap().1, 1);
+    assert_eq!(run("L50\nR100").unwrap().1, 2);
+    assert_eq!(run("L50\nL100").unwrap().1, 2);
+    assert_eq!(run("L50\nR199").unwrap().1, 2);
+    assert_eq!(run("L50\nL199").unwrap().1, 2);
+    assert_eq!(run("L50\nR200").unwrap().1, 3);
+    assert_eq!(run("L50\nL200").unwrap().1, 3);
+}
+
+#[test]
+fn malformed() {
+    assert!(run("R50\n\nL5").is_err());
+    assert!(run("X5").is_err());
+    assert!(run("Lfive").is_err());
 }
 
 #[test]
@@ -67,5 +77,5 @@ L99
 R14
 L82
 ";
-    assert_eq!(run(input), (3, 6));
+    assert_eq!(run(input), Ok((3, 6)));
 }
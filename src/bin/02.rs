@@ -47,11 +47,6 @@ fn test_is_invalid_1() {
     assert!(is_invalid_1(1188511885));
 }
 
-#[test]
-fn test_part_1() {
-    assert_eq!(run(&aoc::example!(0)).0, 1227775554);
-}
-
 fn is_invalid_2(id: u64) -> bool {
     if id == 0 {
         return false;
@@ -90,9 +85,6 @@ fn test_is_invalid_2() {
     assert!(is_invalid_2(2121212121));
 }
 
-#[test]
-fn test_part_2() {
-    assert_eq!(run(&aoc::example!(0)).1, 4174379265);
-}
+aoc::example_tests!(run, 0 => (1227775554, 4174379265));
 
 aoc::main!(run);
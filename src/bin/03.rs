@@ -36,7 +36,6 @@ fn test_part_1() {
     assert_eq!(max_joltage("811111111111119", 2), 89);
     assert_eq!(max_joltage("234234234234278", 2), 78);
     assert_eq!(max_joltage("818181911112111", 2), 92);
-    assert_eq!(run(&aoc::example!(0)).0, 357);
 }
 
 #[test]
@@ -45,6 +44,8 @@ fn test_part_2() {
     assert_eq!(max_joltage("811111111111119", 12), 811111111119);
     assert_eq!(max_joltage("234234234234278", 12), 434234234278);
     assert_eq!(max_joltage("818181911112111", 12), 888911112111);
-    assert_eq!(run(&aoc::example!(0)).1, 3121910778619);
 }
+
+aoc::example_tests!(run, 0 => (357, 3121910778619));
+
 aoc::main!(run);
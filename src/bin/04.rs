@@ -80,14 +80,6 @@ fn run(input: &str) -> (usize, usize) {
     (part_1, part_2)
 }
 
-#[test]
-fn test_part_1() {
-    assert_eq!(run(&aoc::example!(0)).0, 13);
-}
-
-#[test]
-fn test_part_2() {
-    assert_eq!(run(&aoc::example!(0)).1, 43);
-}
+aoc::example_tests!(run, 0 => (13, 43));
 
 aoc::main!(run);
@@ -37,14 +37,6 @@ fn run(input: &str) -> (u64, u64) {
     (part_1, part_2)
 }
 
-#[test]
-fn test_part_1() {
-    assert_eq!(run(&aoc::example!(0)).0, 3);
-}
-
-#[test]
-fn test_part_2() {
-    assert_eq!(run(&aoc::example!(0)).1, 14);
-}
+aoc::example_tests!(run, 0 => (3, 14));
 
 aoc::main!(run);
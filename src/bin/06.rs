@@ -2,6 +2,7 @@ use std::iter;
 
 use aoc2025::grid::{Grid, Idx};
 
+#[aoc::part(1)]
 fn part_1(input: &str) -> u64 {
     let grid = Grid::from_rows(input.lines().map(|line| line.split_whitespace()));
     let size = grid.size();
@@ -19,6 +20,7 @@ fn part_1(input: &str) -> u64 {
         .sum()
 }
 
+#[aoc::part(2)]
 fn part_2(input: &str) -> u64 {
     let grid = Grid::from_rows(input.lines().map(|line| line.chars()));
     let size = grid.size();
@@ -33,7 +35,7 @@ fn part_2(input: &str) -> u64 {
             let numbers = (from_x..to_x).map(|x| {
                 (0..size.y - 1)
                     .map(|y| grid[Idx { x, y }])
-                    .filter(|c| c.is_digit(10))
+                    .filter(|c| c.is_ascii_digit())
                     .collect::<String>()
                     .parse::<u64>()
                     .unwrap()
@@ -51,18 +53,4 @@ fn part_2(input: &str) -> u64 {
         .sum()
 }
 
-fn run(input: &str) -> (u64, u64) {
-    (part_1(input), part_2(input))
-}
-
-#[test]
-fn test_part_1() {
-    assert_eq!(part_1(&aoc::example!(0)), 4277556);
-}
-
-#[test]
-fn test_part_2() {
-    assert_eq!(part_2(&aoc::example!(0)), 3263827);
-}
-
-aoc::main!(run);
+aoc::example_tests!(part_1, part_2, 0 => (4277556, 3263827));
@@ -46,14 +46,6 @@ fn run(input: &str) -> (u64, u64) {
     (part_1, part_2)
 }
 
-#[test]
-fn test_part_1() {
-    assert_eq!(run(&aoc::example!(0)).0, 21);
-}
-
-#[test]
-fn test_part_2() {
-    assert_eq!(run(&aoc::example!(0)).1, 40);
-}
+aoc::example_tests!(run, 0 => (21, 40));
 
 aoc::main!(run);
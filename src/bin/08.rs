@@ -98,18 +98,6 @@ fn part_2(input: &str) -> u64 {
     }
 }
 
-fn run(input: &str) -> (u64, u64) {
-    (part_1(input, 1000), part_2(input))
-}
-
-#[test]
-fn test_part_1() {
-    assert_eq!(part_1(&aoc::example!(0), 10), 40);
-}
-
-#[test]
-fn test_part_2() {
-    assert_eq!(part_2(&aoc::example!(0)), 25272);
-}
+aoc::example_tests!(|input| part_1(input, 10), part_2, 0 => (40, 25272));
 
-aoc::main!(run);
+aoc::main!(|input| part_1(input, 1000), part_2);
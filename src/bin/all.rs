@@ -0,0 +1 @@
+aoc::all!();
@@ -90,7 +90,7 @@ impl<T> Grid<T> {
     }
 
     pub fn contains_idx(&self, idx: Idx) -> bool {
-        (0..self.size.x as isize).contains(&idx.x) && (0..self.size.y as isize).contains(&idx.y)
+        (0..self.size.x).contains(&idx.x) && (0..self.size.y).contains(&idx.y)
     }
 
     pub fn try_get(&self, idx: Idx) -> Option<&T> {
@@ -128,7 +128,7 @@ where
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for y in 0..self.size.y {
             if y > 0 {
-                write!(f, "\n")?;
+                writeln!(f)?;
             }
             for x in 0..self.size.x {
                 write!(f, "{}", self[Idx { x, y }])?;
@@ -137,3 +137,10 @@ where
         Ok(())
     }
 }
+
+/// A grid of pixels is shown as a drawing, which the runner reads as letters if it can.
+impl aoc::Answer for Grid<bool> {
+    fn show(&self) -> String {
+        aoc::draw((0..self.size.y).map(|y| (0..self.size.x).map(move |x| self[Idx { x, y }])))
+    }
+}
@@ -0,0 +1,15 @@
+//! Like a binary with a name such as `custom.rs`, this target does not say which day it is, so the
+//! macros are told.
+
+fn run(input: &str) -> (usize, usize) {
+    (input.lines().count(), input.len())
+}
+
+aoc::example_tests!(run, 0 => (4, 64), day = 3);
+
+#[test]
+fn examples() {
+    assert!(aoc::example!(0, day = 3).starts_with("987654321111111\n"));
+    assert_eq!(aoc::example_answer!(0, 2, year = 2025, day = 3), "3121910778619");
+    aoc::assert_example!(357, 0, 1, day = 3);
+}